@@ -0,0 +1,141 @@
+use std::fs::read_to_string;
+
+use anyhow::{Context, Result};
+
+use crate::backend::root::Escalation;
+use crate::cmd::run_external_command;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Alpine {
+    pub escalation: Escalation,
+}
+impl Alpine {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            escalation: Escalation::new(config),
+        }
+    }
+}
+
+/// The packages the user asked for, one dependency per line.
+const WORLD_FILE: Text = "/etc/apk/world";
+/// The database of all installed packages.
+const INSTALLED_DB: Text = "/lib/apk/db/installed";
+
+impl Backend for Alpine {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "apk".to_string(),
+            section: "alpine",
+            switches_info: &["info"],
+            switches_install: &["add"],
+            switches_noconfirm: &[],
+            switches_remove: &["del"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            versions: true,
+            requires_root: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let content = read_to_string(INSTALLED_DB)
+            .with_context(|| format!("reading installed database {INSTALLED_DB}"))?;
+        Ok(parse_installed_db(&content))
+    }
+
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        let content = read_to_string(WORLD_FILE)
+            .with_context(|| format!("reading world file {WORLD_FILE}"))?;
+        Ok(parse_world(&content))
+    }
+
+    /// Version constraints and repository tags are only relevant for installing.
+    fn as_installed(&self, package: &Package) -> Package {
+        strip_constraint(&package.to_string()).into()
+    }
+
+    /// Install the specified packages.
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
+        cmd.args(backend_info.switches_install);
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Remove the specified packages.
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
+        cmd.args(backend_info.switches_remove);
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+}
+
+/// Parse the world file. Every line holds a dependency that may be followed by a
+/// version constraint (`foo>=1.0`) or repository tag (`foo@edge`). Lines starting
+/// with `!` forbid a package and are ignored.
+fn parse_world(content: &str) -> Packages {
+    content
+        .split_whitespace()
+        .filter(|dependency| !dependency.starts_with('!'))
+        .map(strip_constraint)
+        .filter(|name| !name.is_empty())
+        .map(Package::from)
+        .collect()
+}
+
+/// Parse the installed database. Packages are separated by empty lines, and
+/// the name of each package is stored in the line starting with `P:`.
+fn parse_installed_db(content: &str) -> Packages {
+    content
+        .lines()
+        .filter_map(|line| line.strip_prefix("P:"))
+        .map(Package::from)
+        .collect()
+}
+
+/// Remove a version constraint or repository tag from a dependency.
+fn strip_constraint(dependency: &str) -> &str {
+    dependency
+        .split(['<', '>', '=', '~', '@'])
+        .next()
+        .expect("split always yields at least one element")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_and_installed_db() {
+        let world = "alpine-base\nbusybox>=1.36\nneovim@edge\n!docs\n";
+        let names: Vec<_> = parse_world(world).into_iter().map(|p| p.name).collect();
+        assert_eq!(names, ["alpine-base", "busybox", "neovim"]);
+
+        let installed = "C:Q1abc=\nP:musl\nV:1.2.4-r2\n\nC:Q1def=\nP:busybox\nV:1.36.1-r5\n";
+        let names: Vec<_> = parse_installed_db(installed)
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, ["busybox", "musl"]);
+    }
+}
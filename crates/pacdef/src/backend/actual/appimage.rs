@@ -0,0 +1,197 @@
+use std::fs::{metadata, read_dir, remove_file};
+use std::io::ErrorKind::NotFound;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use crate::cmd::run_external_command;
+use crate::prelude::*;
+use crate::recorder::edit_file;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AppImage {
+    /// The directory that holds the AppImages, `~/Applications` if `None`.
+    pub dir: Option<PathBuf>,
+}
+impl AppImage {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            dir: config.appimage_dir.clone(),
+        }
+    }
+
+    fn get_dir(&self) -> Result<PathBuf> {
+        if let Some(dir) = &self.dir {
+            return Ok(dir.clone());
+        }
+
+        let mut dir = get_home_dir().context("falling back to $HOME/Applications")?;
+        dir.push("Applications");
+        Ok(dir)
+    }
+
+    fn get_file(&self, package: &Package) -> Result<PathBuf> {
+        let mut file = self.get_dir()?;
+        file.push(format!("{}.{EXTENSION}", package.name));
+        Ok(file)
+    }
+}
+
+const EXTENSION: Text = "AppImage";
+
+impl Backend for AppImage {
+    /// AppImages are installed by copying them with `install`.
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "install".to_string(),
+            section: "appimage",
+            switches_info: &[],
+            switches_install: &["-D", "--mode=755"],
+            switches_noconfirm: &[],
+            switches_remove: &[],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let dir = self.get_dir()?;
+
+        let entries = match read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == NotFound => {
+                log::warn!("no AppImage dir found. Assuming no AppImages installed yet.");
+                return Ok(Packages::new());
+            }
+            Err(err) => bail!(err),
+        };
+
+        let mut result = Packages::new();
+
+        for entry in entries {
+            let path = entry?.path();
+
+            // Only the exact extension counts, since the file of a package is
+            // always named `<name>.AppImage`.
+            let is_appimage = path
+                .extension()
+                .is_some_and(|extension| extension == EXTENSION);
+
+            if !path.is_file() || !is_appimage {
+                continue;
+            }
+
+            if let Some(stem) = path.file_stem() {
+                result.insert(stem.to_string_lossy().as_ref().into());
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        self.get_all_installed_packages()
+            .context("getting all installed packages")
+    }
+
+    /// Copy each AppImage from its source into the AppImage dir and mark it as
+    /// executable. The source is written after the package, like
+    /// `name = /path/to/file.AppImage`.
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        for p in packages {
+            let source = get_source(p)?;
+
+            let mut cmd = Command::new(&backend_info.binary);
+            cmd.args(backend_info.switches_install);
+            cmd.arg(source);
+            cmd.arg(self.get_file(p)?);
+
+            run_external_command(cmd).with_context(|| format!("installing {p}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Remove the specified packages by deleting the files from the AppImage dir.
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        for p in packages {
+            let file = self.get_file(p)?;
+            edit_file(format_args!("delete {}", file.display()), || {
+                remove_file(&file).with_context(|| format!("deleting {file:?}"))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn show_package_info(&self, package: &Package) -> Result<()> {
+        let file = self.get_file(package)?;
+        let size = metadata(&file)
+            .with_context(|| format!("reading metadata of {file:?}"))?
+            .len();
+
+        println!("{}, {size} bytes", file.to_string_lossy());
+
+        Ok(())
+    }
+}
+
+/// Get the source of an AppImage from the arguments of the package. Only local
+/// files are supported, either as path or as `file://` URL. The arguments of a
+/// package are split at whitespace, so sources with whitespace are rejected
+/// instead of being guessed.
+fn get_source(package: &Package) -> Result<PathBuf> {
+    if let Some((name, _)) = package.to_string().split_once('=') {
+        let name = name.trim();
+        bail!(
+            "{package} is not a valid AppImage, expected `{name} = <source>` with spaces around `=`"
+        );
+    }
+
+    let source = match package.arguments.as_slice() {
+        [equals, source] if equals == "=" => source,
+        [equals, _, ..] if equals == "=" => {
+            bail!("the source of {package} contains whitespace, which is not supported")
+        }
+        _ => bail!("{package} has no source, expected `{package} = <source>`"),
+    };
+
+    if let Some(path) = source.strip_prefix("file://") {
+        return Ok(path.into());
+    }
+
+    if source.contains("://") {
+        bail!("{source} is not supported, only local paths and file:// URLs are");
+    }
+
+    Ok(source.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source() {
+        assert_eq!(
+            get_source(&Package::from("obsidian = /apps/Obsidian.AppImage")).expect("valid source"),
+            PathBuf::from("/apps/Obsidian.AppImage")
+        );
+        assert_eq!(
+            get_source(&Package::from("obsidian = file:///apps/Obsidian.AppImage"))
+                .expect("valid source"),
+            PathBuf::from("/apps/Obsidian.AppImage")
+        );
+
+        assert!(get_source(&Package::from("obsidian=/apps/Obsidian.AppImage")).is_err());
+        assert!(get_source(&Package::from("obsidian= /apps/Obsidian.AppImage")).is_err());
+        assert!(get_source(&Package::from("obsidian = /my apps/Obsidian.AppImage")).is_err());
+        assert!(get_source(&Package::from("obsidian")).is_err());
+        assert!(get_source(&Package::from(
+            "obsidian = https://example.com/Obsidian.AppImage"
+        ))
+        .is_err());
+    }
+}
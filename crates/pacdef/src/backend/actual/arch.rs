@@ -35,6 +35,15 @@ impl Backend for Arch {
         }
     }
 
+    /// pacman needs root privileges, but the AUR helper escalates them by itself
+    /// and refuses to be run as root, so pacdef must not escalate it.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            repositories: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
     fn get_all_installed_packages(&self) -> Result<Packages> {
         let alpm_packages = get_all_installed_packages_from_alpm()
             .context("getting all installed packages from alpm")?;
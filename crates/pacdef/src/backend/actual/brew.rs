@@ -0,0 +1,141 @@
+use std::process::Command;
+
+use anyhow::Result;
+
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Brew {}
+impl Brew {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+impl Default for Brew {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The tap that holds all formulae by default. `brew` only prints the name for
+/// these.
+const DEFAULT_FORMULA_TAP: Text = "homebrew/core";
+
+/// The tap that holds all casks by default. `brew` only prints the token for
+/// these, so we prefix it to be able to tell casks and formulae apart.
+const DEFAULT_CASK_TAP: Text = "homebrew/cask";
+
+/// These switches are responsible for
+/// getting the formulae explicitly installed by the user
+const SWITCHES_FETCH_USER: Switches = &["leaves", "--installed-on-request"];
+
+/// These switches are responsible for
+/// getting all the formulae installed on the system
+const SWITCHES_FETCH_GLOBAL: Switches = &["list", "--formula", "--full-name", "-1"];
+
+/// These switches are responsible for
+/// getting all the casks installed on the system
+const SWITCHES_FETCH_CASKS: Switches = &["list", "--cask", "--full-name", "-1"];
+
+impl Backend for Brew {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "brew".to_string(),
+            section: "brew",
+            switches_info: &["info"],
+            switches_install: &["install"],
+            switches_noconfirm: &[],
+            switches_remove: &["uninstall"],
+            switches_make_dependency: Some(&["tab", "--no-installed-on-request"]),
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            repositories: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let mut packages = self.run_list_command(SWITCHES_FETCH_GLOBAL)?;
+        packages.extend(self.get_installed_casks()?);
+        Ok(packages)
+    }
+
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        let mut packages = self.run_list_command(SWITCHES_FETCH_USER)?;
+        packages.extend(self.get_installed_casks()?);
+        Ok(packages)
+    }
+
+    /// Formulae from the default tap like `homebrew/core/ripgrep` are listed by
+    /// their name only.
+    fn as_installed(&self, package: &Package) -> Package {
+        let full_name = package.to_string();
+
+        full_name
+            .strip_prefix(DEFAULT_FORMULA_TAP)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .map_or_else(|| package.clone(), Package::from)
+    }
+}
+
+impl Brew {
+    fn run_list_command(&self, args: &[&str]) -> Result<Packages> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(args);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        Ok(output.lines().map(Package::from).collect())
+    }
+
+    /// Casks cannot be installed as dependency, so every installed cask is
+    /// considered explicit.
+    fn get_installed_casks(&self) -> Result<Packages> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(SWITCHES_FETCH_CASKS);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        Ok(output.lines().map(create_cask_package).collect())
+    }
+}
+
+fn create_cask_package(token: &str) -> Package {
+    if token.contains('/') {
+        token.into()
+    } else {
+        format!("{DEFAULT_CASK_TAP}/{token}").into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_taps() {
+        let brew = Brew::new();
+        assert_eq!(
+            brew.as_installed(&Package::from("homebrew/core/ripgrep")),
+            Package::from("ripgrep")
+        );
+        assert_eq!(
+            brew.as_installed(&Package::from("ripgrep")),
+            Package::from("ripgrep")
+        );
+        assert_eq!(
+            brew.as_installed(&Package::from("hashicorp/tap/terraform")),
+            Package::from("hashicorp/tap/terraform")
+        );
+
+        assert_eq!(
+            create_cask_package("firefox"),
+            Package::from("homebrew/cask/firefox")
+        );
+        assert_eq!(
+            create_cask_package("homebrew/cask-fonts/font-fira-code"),
+            Package::from("homebrew/cask-fonts/font-fira-code")
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::cmd::run_external_command;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Conda {
+    pub binary: String,
+    /// The name of the environment to manage.
+    pub env: String,
+}
+impl Conda {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            binary: config.conda_binary.clone(),
+            env: config.conda_env.clone(),
+        }
+    }
+
+    /// Build a command for the managed environment. The switches are understood by
+    /// conda, mamba and micromamba alike.
+    fn build_command(&self, switches: Switches) -> Command {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(switches);
+        cmd.args(["--name", &self.env]);
+        cmd
+    }
+
+    fn run_json_command(&self, switches: Switches) -> Result<Value> {
+        let mut cmd = self.build_command(switches);
+        cmd.arg("--json");
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        serde_json::from_str(&output).with_context(|| format!("parsing JSON from {}", self.binary))
+    }
+}
+
+impl Backend for Conda {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: self.binary.clone(),
+            section: "conda",
+            switches_info: &["search", "--info"],
+            switches_install: &["install"],
+            switches_noconfirm: &["--yes"],
+            switches_remove: &["remove"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            versions: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let json = self.run_json_command(&["list"])?;
+        extract_packages(&json)
+    }
+
+    /// The history of the environment holds the packages that were requested
+    /// explicitly.
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        let json = self.run_json_command(&["env", "export", "--from-history"])?;
+        extract_history(&json)
+    }
+
+    /// Channels and version constraints like `conda-forge::numpy>=1.26` are only
+    /// relevant for installing.
+    fn as_installed(&self, package: &Package) -> Package {
+        strip_match_spec(&package.to_string()).into()
+    }
+
+    /// Install the specified packages.
+    fn install_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = self.build_command(backend_info.switches_install);
+
+        if noconfirm {
+            cmd.args(backend_info.switches_noconfirm);
+        }
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Remove the specified packages.
+    fn remove_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = self.build_command(backend_info.switches_remove);
+
+        if noconfirm {
+            cmd.args(backend_info.switches_noconfirm);
+        }
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+}
+
+/// Extract the package names from the output of `list --json`, which is an array
+/// with one object per package.
+fn extract_packages(json: &Value) -> Result<Packages> {
+    json.as_array()
+        .context("getting array of packages")?
+        .iter()
+        .map(|node| {
+            node["name"]
+                .as_str()
+                .map(Package::from)
+                .context("getting name of package")
+        })
+        .collect()
+}
+
+/// Extract the package names from the output of `env export --from-history --json`.
+/// The dependencies are match specs like `python=3.11`. Nested objects hold
+/// packages of other package managers like pip and are ignored.
+fn extract_history(json: &Value) -> Result<Packages> {
+    let Some(dependencies) = json.get("dependencies") else {
+        return Ok(Packages::new());
+    };
+
+    Ok(dependencies
+        .as_array()
+        .context("getting 'dependencies' array")?
+        .iter()
+        .filter_map(Value::as_str)
+        .map(strip_match_spec)
+        .map(Package::from)
+        .collect())
+}
+
+/// Reduce a match spec like `conda-forge::numpy>=1.26` to the name of the package.
+fn strip_match_spec(spec: &str) -> &str {
+    let without_channel = spec
+        .rsplit("::")
+        .next()
+        .expect("split always yields at least one element");
+
+    without_channel
+        .split(['=', '<', '>', '!', '~', ' ', '['])
+        .next()
+        .expect("split always yields at least one element")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history() {
+        let json = serde_json::json!({
+            "name": "base",
+            "channels": ["conda-forge"],
+            "dependencies": [
+                "python=3.11",
+                "conda-forge::numpy>=1.26",
+                "pandas",
+                {"pip": ["requests"]}
+            ]
+        });
+
+        let names: Vec<_> = extract_history(&json)
+            .expect("valid history")
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, ["numpy", "pandas", "python"]);
+    }
+}
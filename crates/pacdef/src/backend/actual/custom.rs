@@ -0,0 +1,325 @@
+use std::process::Command;
+
+use anyhow::{bail, ensure, Context, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::backend::root::Escalation;
+use crate::cmd::{run_external_command, run_read_only_command};
+use crate::prelude::*;
+
+/// A backend that is defined by the user in the config. Every action is a command
+/// template, i.e. a list of the binary and its arguments.
+///
+/// In a template, the argument `{packages}` is replaced by all packages the action
+/// applies to, and `{package}` by the single package for `info`. Without such a
+/// placeholder, the packages are appended to the command.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Custom {
+    /// The name of the section in the group files.
+    #[serde(deserialize_with = "deserialize_section")]
+    pub section: Text,
+    /// The command that lists the explicitly installed packages.
+    pub list_explicit: Vec<String>,
+    /// The command that lists all installed packages, `list_explicit` if unset.
+    #[serde(default)]
+    pub list_all: Option<Vec<String>>,
+    /// The command that installs packages.
+    pub install: Vec<String>,
+    /// The command that removes packages.
+    pub remove: Vec<String>,
+    /// The command that shows information about a package.
+    #[serde(default)]
+    pub info: Option<Vec<String>>,
+    /// The command that marks packages as dependencies.
+    #[serde(default)]
+    pub make_dependency: Option<Vec<String>>,
+    /// Whether installing, removing and marking packages requires root privileges.
+    #[serde(default)]
+    pub privileged: bool,
+    /// How the output of the list commands is parsed.
+    #[serde(default)]
+    pub output: Output,
+    /// How privileged commands are run. This is not part of the definition of the
+    /// backend, but set from the config by [`Custom::with_escalation`].
+    #[serde(skip)]
+    pub escalation: Option<Escalation>,
+}
+
+/// The format of the output of the list commands of a [`Custom`] backend.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "format", rename_all = "lowercase")]
+pub enum Output {
+    /// One package per line. Anything after the first whitespace, like a version,
+    /// is ignored.
+    #[default]
+    Lines,
+    /// A JSON document. `pointer` is a JSON pointer to either an array or an
+    /// object. For an array of strings, every string is a package. For an array of
+    /// objects, `name` is a JSON pointer to the package within each object. For an
+    /// object, every key is a package.
+    Json {
+        #[serde(default)]
+        pointer: String,
+        #[serde(default)]
+        name: Option<String>,
+    },
+}
+
+/// The switches of custom backends are part of their command templates.
+const NO_SWITCHES: Switches = &[];
+/// The placeholder for all packages of an action.
+const PACKAGES: Text = "{packages}";
+/// The placeholder for the single package of `info`.
+const PACKAGE: Text = "{package}";
+
+/// The section names of custom backends are needed for the lifetime of the
+/// program, like those of the built-in backends.
+fn deserialize_section<'de, D>(deserializer: D) -> std::result::Result<Text, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let section = String::deserialize(deserializer)?;
+    Ok(section.leak())
+}
+
+impl Custom {
+    /// Return the backend with the privilege escalation from the config.
+    pub fn with_escalation(&self, config: &Config) -> Self {
+        Self {
+            escalation: Some(Escalation::new(config)),
+            ..self.clone()
+        }
+    }
+
+    /// Build the command for a template, with the placeholders replaced by the
+    /// packages.
+    fn build_command(
+        &self,
+        template: &[String],
+        packages: &[String],
+        privileged: bool,
+    ) -> Result<Command> {
+        let (binary, arguments) = template
+            .split_first()
+            .with_context(|| format!("command of [{}] is empty", self.section))?;
+
+        let mut cmd = if privileged {
+            self.escalation
+                .as_ref()
+                .with_context(|| format!("privilege escalation of [{}] is not set", self.section))?
+                .build_command(self, binary)
+        } else {
+            Command::new(binary)
+        };
+
+        let mut has_placeholder = false;
+
+        for argument in arguments {
+            if argument == PACKAGES || argument == PACKAGE {
+                cmd.args(packages);
+                has_placeholder = true;
+            } else {
+                cmd.arg(argument);
+            }
+        }
+
+        if !has_placeholder {
+            cmd.args(packages);
+        }
+
+        Ok(cmd)
+    }
+
+    fn run_for_packages(&self, template: &[String], packages: &Packages) -> Result<()> {
+        let packages: Vec<_> = packages.iter().map(ToString::to_string).collect();
+        let cmd = self.build_command(template, &packages, self.privileged)?;
+        run_external_command(cmd)
+    }
+
+    fn list(&self, template: &[String]) -> Result<Packages> {
+        let mut cmd = self.build_command(template, &[], false)?;
+
+        let output = cmd
+            .output()
+            .with_context(|| format!("running list command of [{}]", self.section))?;
+        ensure!(
+            output.status.success(),
+            "list command of [{}] failed with {}",
+            self.section,
+            output.status
+        );
+
+        let stdout = String::from_utf8(output.stdout)?;
+        self.output.parse(&stdout)
+    }
+}
+
+impl Output {
+    fn parse(&self, output: &str) -> Result<Packages> {
+        match self {
+            Self::Lines => Ok(output
+                .lines()
+                .filter_map(|line| line.split_whitespace().next())
+                .map(Package::from)
+                .collect()),
+            Self::Json { pointer, name } => {
+                let json: Value = serde_json::from_str(output).context("parsing JSON")?;
+                let value = json
+                    .pointer(pointer)
+                    .with_context(|| format!("JSON pointer '{pointer}' does not exist"))?;
+
+                extract_packages(value, name.as_deref())
+            }
+        }
+    }
+}
+
+/// Extract the packages from the JSON value a pointer refers to.
+fn extract_packages(value: &Value, name: Option<&str>) -> Result<Packages> {
+    if let Some(object) = value.as_object() {
+        return Ok(object
+            .keys()
+            .map(|key| Package::from(key.as_str()))
+            .collect());
+    }
+
+    let Some(array) = value.as_array() else {
+        bail!("JSON pointer refers to neither an array nor an object");
+    };
+
+    let mut result = Packages::new();
+
+    for element in array {
+        let element = match name {
+            Some(name) => element
+                .pointer(name)
+                .with_context(|| format!("JSON pointer '{name}' does not exist"))?,
+            None => element,
+        };
+
+        let package = element.as_str().context("package is not a string")?;
+        result.insert(package.into());
+    }
+
+    Ok(result)
+}
+
+impl Backend for Custom {
+    /// The binary is the first element of the `list_explicit` command.
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: self.list_explicit.first().cloned().unwrap_or_default(),
+            section: self.section,
+            switches_info: NO_SWITCHES,
+            switches_install: NO_SWITCHES,
+            switches_noconfirm: NO_SWITCHES,
+            switches_remove: NO_SWITCHES,
+            switches_make_dependency: self.make_dependency.as_ref().map(|_| NO_SWITCHES),
+        }
+    }
+
+    /// There is no template to skip the confirmation, so pacdef cannot know
+    /// whether the commands ask for it.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            show_info: self.info.is_some(),
+            noconfirm: false,
+            requires_root: self.privileged,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let template = self.list_all.as_ref().unwrap_or(&self.list_explicit);
+        self.list(template)
+    }
+
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        self.list(&self.list_explicit)
+    }
+
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        self.run_for_packages(&self.install, packages)
+    }
+
+    fn make_dependency(&self, packages: &Packages) -> Result<()> {
+        let Some(template) = &self.make_dependency else {
+            bail!(Error::Unsupported(self.section, Operation::MakeDependency));
+        };
+
+        self.run_for_packages(template, packages)
+    }
+
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        self.run_for_packages(&self.remove, packages)
+    }
+
+    fn show_package_info(&self, package: &Package) -> Result<()> {
+        let Some(template) = &self.info else {
+            bail!(Error::Unsupported(self.section, Operation::ShowInfo));
+        };
+
+        let cmd = self.build_command(template, &[package.to_string()], false)?;
+        run_read_only_command(cmd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_output() {
+        let names = |packages: Packages| -> Vec<String> {
+            packages.into_iter().map(|p| p.to_string()).collect()
+        };
+
+        let lines = Output::Lines
+            .parse("dune 3.12.1\nocaml\n\n")
+            .expect("valid output");
+        assert_eq!(names(lines), ["dune", "ocaml"]);
+
+        let output = Output::Json {
+            pointer: "/installed".into(),
+            name: Some("/name".into()),
+        };
+        let json = r#"{"installed": [{"name": "ctx"}, {"name": "ns"}]}"#;
+        assert_eq!(
+            names(output.parse(json).expect("valid output")),
+            ["ctx", "ns"]
+        );
+
+        let output = Output::Json {
+            pointer: "/dependencies".into(),
+            name: None,
+        };
+        let json = r#"{"dependencies": {"left-pad": {}, "lodash": {}}}"#;
+        assert_eq!(
+            names(output.parse(json).expect("valid output")),
+            ["left-pad", "lodash"]
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        let config: Config = toml::from_str(
+            r#"
+            [[custom_backends]]
+            section = "krew"
+            list_explicit = ["kubectl", "krew", "list"]
+            install = ["kubectl", "krew", "install", "{packages}"]
+            remove = ["kubectl", "krew", "uninstall"]
+            output = { format = "json", pointer = "/items", name = "/name" }
+            "#,
+        )
+        .expect("valid config");
+
+        let backend = AnyBackend::from_section("krew", &config).expect("custom backend");
+        assert_eq!(backend.backend_info().binary, "kubectl");
+        let capabilities = backend.capabilities();
+        assert!(!capabilities.make_dependency);
+        assert!(!capabilities.show_info);
+        assert!(!capabilities.noconfirm);
+    }
+}
@@ -2,20 +2,19 @@ use anyhow::Result;
 use rust_apt::cache::PackageSort;
 use rust_apt::new_cache;
 
-use crate::backend::root::build_base_command_with_privileges;
+use crate::backend::root::Escalation;
 use crate::cmd::run_external_command;
 use crate::prelude::*;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Debian {}
-impl Debian {
-    pub fn new() -> Self {
-        Self {}
-    }
+pub struct Debian {
+    pub escalation: Escalation,
 }
-impl Default for Debian {
-    fn default() -> Self {
-        Self::new()
+impl Debian {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            escalation: Escalation::new(config),
+        }
     }
 }
 
@@ -32,6 +31,13 @@ impl Backend for Debian {
         }
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_root: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
     fn get_all_installed_packages(&self) -> Result<Packages> {
         let cache = new_cache!()?;
         let sort = PackageSort::default().installed();
@@ -55,7 +61,7 @@ impl Backend for Debian {
     }
 
     fn make_dependency(&self, packages: &Packages) -> Result<()> {
-        let mut cmd = build_base_command_with_privileges("apt-mark");
+        let mut cmd = self.escalation.build_command(self, "apt-mark");
         cmd.arg("auto");
         for p in packages {
             cmd.arg(format!("{p}"));
@@ -68,7 +74,7 @@ impl Backend for Debian {
     fn install_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
         let backend_info = self.backend_info();
 
-        let mut cmd = build_base_command_with_privileges(&backend_info.binary);
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
 
         cmd.args(backend_info.switches_install);
 
@@ -87,7 +93,7 @@ impl Backend for Debian {
     fn remove_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
         let backend_info = self.backend_info();
 
-        let mut cmd = build_base_command_with_privileges(&backend_info.binary);
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
         cmd.args(backend_info.switches_remove);
 
         if noconfirm {
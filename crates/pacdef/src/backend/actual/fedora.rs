@@ -2,19 +2,19 @@ use std::process::Command;
 
 use anyhow::Result;
 
-use crate::cmd::run_external_command;
+use crate::backend::root::Escalation;
+use crate::cmd::{run_external_command, run_read_only_command};
 use crate::prelude::*;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Fedora {}
-impl Fedora {
-    pub fn new() -> Self {
-        Self {}
-    }
+pub struct Fedora {
+    pub escalation: Escalation,
 }
-impl Default for Fedora {
-    fn default() -> Self {
-        Self::new()
+impl Fedora {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            escalation: Escalation::new(config),
+        }
     }
 }
 
@@ -53,6 +53,14 @@ impl Backend for Fedora {
         }
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            repositories: true,
+            requires_root: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
     fn get_all_installed_packages(&self) -> Result<Packages> {
         let mut cmd = Command::new(self.backend_info().binary);
         cmd.args(SWITCHES_FETCH_GLOBAL);
@@ -77,8 +85,7 @@ impl Backend for Fedora {
     fn install_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
         let backend_info = self.backend_info();
 
-        let mut cmd = Command::new("sudo");
-        cmd.arg(backend_info.binary);
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
         cmd.args(backend_info.switches_install);
 
         if noconfirm {
@@ -103,8 +110,7 @@ impl Backend for Fedora {
     fn remove_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
         let backend_info = self.backend_info();
 
-        let mut cmd = Command::new("sudo");
-        cmd.arg(backend_info.binary);
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
         cmd.args(backend_info.switches_remove);
 
         if noconfirm {
@@ -125,11 +131,7 @@ impl Backend for Fedora {
         cmd.args(backend_info.switches_info);
         cmd.arg(&package.name);
 
-        run_external_command(cmd)
-    }
-
-    fn make_dependency(&self, _: &Packages) -> Result<()> {
-        panic!("Not supported by the package manager!")
+        run_read_only_command(cmd)
     }
 }
 
@@ -2,7 +2,7 @@ use std::process::Command;
 
 use anyhow::Result;
 
-use crate::cmd::run_external_command;
+use crate::cmd::{run_external_command, run_read_only_command};
 use crate::prelude::*;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -79,10 +79,6 @@ impl Backend for Flatpak {
         run_external_command(cmd)
     }
 
-    fn make_dependency(&self, _: &Packages) -> Result<()> {
-        panic!("not supported by {}", self.backend_info().binary)
-    }
-
     /// Remove the specified packages.
     fn remove_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
         let backend_info = self.backend_info();
@@ -111,6 +107,6 @@ impl Backend for Flatpak {
         cmd.args(self.get_switches_runtime());
         cmd.arg(format!("{package}"));
 
-        run_external_command(cmd)
+        run_read_only_command(cmd)
     }
 }
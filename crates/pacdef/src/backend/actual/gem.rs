@@ -0,0 +1,136 @@
+use std::process::Command;
+
+use anyhow::Result;
+
+use crate::cmd::run_external_command;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Gem {
+    pub user_install: bool,
+}
+impl Gem {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            user_install: config.gem_user_install,
+        }
+    }
+
+    fn get_switches_runtime(&self) -> Switches {
+        if self.user_install {
+            &["--user-install"]
+        } else {
+            &[]
+        }
+    }
+
+    /// Return the name of every installed gem, and whether it is only installed as
+    /// default gem that ships with Ruby.
+    fn get_installed_gems(&self) -> Result<Vec<(String, bool)>> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(["list", "--local"]);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        Ok(output.lines().filter_map(parse_gem_line).collect())
+    }
+}
+
+impl Backend for Gem {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "gem".to_string(),
+            section: "gem",
+            switches_info: &["info", "--local"],
+            switches_install: &["install"],
+            switches_noconfirm: &[],
+            switches_remove: &["uninstall", "--all", "--executables"],
+            switches_make_dependency: None,
+        }
+    }
+
+    /// gem asks for confirmation if other gems depend on a package that is
+    /// removed, and there is no switch that only skips the question.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            noconfirm: false,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        Ok(self
+            .get_installed_gems()?
+            .into_iter()
+            .map(|(name, _)| name.into())
+            .collect())
+    }
+
+    /// Default gems ship with Ruby and are therefore not considered explicit.
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        Ok(self
+            .get_installed_gems()?
+            .into_iter()
+            .filter(|(_, only_default)| !only_default)
+            .map(|(name, _)| name.into())
+            .collect())
+    }
+
+    /// Install the specified packages.
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = Command::new(backend_info.binary);
+        cmd.args(backend_info.switches_install);
+        cmd.args(self.get_switches_runtime());
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Remove all versions of the specified packages.
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = Command::new(backend_info.binary);
+        cmd.args(backend_info.switches_remove);
+        cmd.args(self.get_switches_runtime());
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+}
+
+/// Parse a line from `gem list` like `rake (13.1.0, default: 13.0.6)`.
+fn parse_gem_line(line: &str) -> Option<(String, bool)> {
+    let (name, versions) = line.split_once(" (")?;
+    let only_default = versions
+        .trim_end_matches(')')
+        .split(", ")
+        .all(|version| version.starts_with("default: "));
+
+    Some((name.to_string(), only_default))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gem_lines() {
+        assert_eq!(
+            parse_gem_line("rake (13.1.0, default: 13.0.6)"),
+            Some(("rake".to_string(), false))
+        );
+        assert_eq!(
+            parse_gem_line("bundler (default: 2.4.10)"),
+            Some(("bundler".to_string(), true))
+        );
+        assert_eq!(parse_gem_line(""), None);
+    }
+}
@@ -0,0 +1,242 @@
+use std::fs::read_to_string;
+use std::io::ErrorKind::NotFound;
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use walkdir::WalkDir;
+
+use crate::backend::root::Escalation;
+use crate::cmd::run_external_command;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Gentoo {
+    pub escalation: Escalation,
+}
+impl Gentoo {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            escalation: Escalation::new(config),
+        }
+    }
+}
+
+/// The atoms the user asked for, one per line.
+const WORLD_FILE: Text = "/var/lib/portage/world";
+/// The sets the user asked for, one per line.
+const WORLD_SETS_FILE: Text = "/var/lib/portage/world_sets";
+/// The database of installed packages, with one dir per `category/name-version`.
+const PKG_DB: Text = "/var/db/pkg";
+
+/// Matches the version at the end of a `name-version` string.
+const VERSION_REGEX: Text = r"-[0-9]+(\.[0-9]+)*[a-z]?(_(alpha|beta|pre|rc|p)[0-9]*)*(-r[0-9]+)?$";
+
+impl Backend for Gentoo {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "emerge".to_string(),
+            section: "gentoo",
+            switches_info: &["--pretend", "--verbose"],
+            switches_install: &["--noreplace"],
+            switches_noconfirm: &[],
+            switches_remove: &["--deselect"],
+            switches_make_dependency: Some(&["--deselect"]),
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            repositories: true,
+            versions: true,
+            requires_root: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let re = Regex::new(VERSION_REGEX)?;
+        let mut result = Packages::new();
+
+        for entry in WalkDir::new(PKG_DB).min_depth(2).max_depth(2) {
+            let entry = entry.with_context(|| format!("reading {PKG_DB}"))?;
+            let name = entry.file_name().to_string_lossy();
+
+            // packages in the process of being merged
+            if name.starts_with('-') {
+                continue;
+            }
+
+            let category = entry
+                .path()
+                .parent()
+                .and_then(|p| p.file_name())
+                .context("getting category of installed package")?
+                .to_string_lossy();
+
+            let name = re.replace(&name, "");
+            result.insert(atom_package(&format!("{category}/{name}")));
+        }
+
+        result.extend(read_world_sets()?);
+
+        Ok(result)
+    }
+
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        let content = read_to_string(WORLD_FILE)
+            .with_context(|| format!("reading world file {WORLD_FILE}"))?;
+
+        let mut result = Packages::new();
+        for atom in content.split_whitespace() {
+            result.insert(atom_package(strip_atom(atom)?));
+        }
+
+        result.extend(read_world_sets()?);
+
+        Ok(result)
+    }
+
+    /// Operators, versions, slots and repositories of atoms are only relevant for
+    /// installing.
+    fn as_installed(&self, package: &Package) -> Package {
+        let atom = package.to_string();
+        strip_atom(&atom).map_or_else(|_| package.clone(), atom_package)
+    }
+
+    /// Install the specified packages.
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
+        cmd.args(backend_info.switches_install);
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Remove the packages from the world file so that they become dependencies.
+    fn make_dependency(&self, packages: &Packages) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
+        cmd.args(
+            backend_info
+                .switches_make_dependency
+                .expect("gentoo should support make dependency"),
+        );
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Remove the packages from the world file, then remove those of them that are
+    /// no longer needed. Other orphans on the system are left alone.
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
+        cmd.args(backend_info.switches_remove);
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd).context("deselecting packages")?;
+
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
+        cmd.arg("--depclean");
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd).context("removing packages that are no longer needed")
+    }
+}
+
+/// Read the sets from the world sets file, which may not exist.
+fn read_world_sets() -> Result<Packages> {
+    let content = match read_to_string(WORLD_SETS_FILE) {
+        Ok(content) => content,
+        Err(err) if err.kind() == NotFound => return Ok(Packages::new()),
+        Err(err) => bail!(err),
+    };
+
+    Ok(content.split_whitespace().map(atom_package).collect())
+}
+
+/// Create a package from an atom without version. The category is part of the
+/// name, rather than being interpreted as repo.
+fn atom_package(atom: &str) -> Package {
+    Package {
+        name: atom.to_string(),
+        repo: None,
+        arguments: vec![],
+    }
+}
+
+/// Reduce an atom like `>=dev-lang/rust-1.75.0:stable::gentoo` to `dev-lang/rust`.
+/// Sets like `@kde-plasma` are returned as they are.
+///
+/// # Errors
+///
+/// This function returns an error if the atom has a version operator, but no
+/// version.
+fn strip_atom(atom: &str) -> Result<&str> {
+    if atom.starts_with('@') {
+        return Ok(atom);
+    }
+
+    let without_operator = atom.trim_start_matches(['!', '<', '>', '=', '~']);
+    let has_operator = without_operator.len() != atom.len();
+
+    let without_repo = without_operator
+        .split("::")
+        .next()
+        .expect("split always yields at least one element");
+    let without_slot = without_repo
+        .split(':')
+        .next()
+        .expect("split always yields at least one element")
+        .trim_end_matches('*');
+
+    if !has_operator {
+        return Ok(without_slot);
+    }
+
+    let re = Regex::new(VERSION_REGEX)?;
+    let version = re
+        .find(without_slot)
+        .with_context(|| format!("atom {atom} has an operator but no version"))?;
+
+    Ok(&without_slot[..version.start()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strip_atom;
+
+    #[test]
+    fn atoms() {
+        let cases = [
+            ("dev-lang/rust", "dev-lang/rust"),
+            (">=dev-lang/rust-1.75.0:stable::gentoo", "dev-lang/rust"),
+            ("=sys-devel/gcc-13.2.1_p20240113-r1", "sys-devel/gcc"),
+            ("~media-libs/x264-0.0.20231114*", "media-libs/x264"),
+            ("app-editors/vim:0", "app-editors/vim"),
+            ("@kde-plasma", "@kde-plasma"),
+        ];
+
+        for (atom, expected) in cases {
+            assert_eq!(strip_atom(atom).expect("valid atom"), expected);
+        }
+
+        assert!(strip_atom(">=dev-lang/rust").is_err());
+    }
+}
@@ -0,0 +1,210 @@
+mod types;
+
+use crate::cmd::{run_external_command, run_read_only_command};
+use crate::prelude::*;
+use anyhow::{Context, Result};
+use std::cmp::Ordering;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use self::types::{GhcupPackage, Tool};
+
+#[derive(Debug, Clone)]
+pub struct Ghcup {
+    /// The installed versions, which are listed once and then used to resolve
+    /// shortened versions in [`Backend::as_installed`].
+    installed: OnceLock<Vec<GhcupPackage>>,
+}
+impl Ghcup {
+    pub fn new() -> Self {
+        Self {
+            installed: OnceLock::new(),
+        }
+    }
+}
+
+// The installed versions are a cache and not part of the identity of the backend.
+impl PartialEq for Ghcup {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Ghcup {}
+
+impl PartialOrd for Ghcup {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ghcup {
+    fn cmp(&self, _: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+impl Default for Ghcup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Backend for Ghcup {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "ghcup".to_string(),
+            section: "ghcup",
+            switches_info: &["whereis"],
+            switches_install: &["install"],
+            switches_noconfirm: &[],
+            switches_remove: &["rm"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            repositories: true,
+            versions: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let installed = self
+            .get_installed_versions()
+            .context("getting installed versions")?;
+        let _ = self.installed.set(installed.clone());
+
+        Ok(installed.iter().map(Package::from).collect())
+    }
+
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        self.get_all_installed_packages()
+            .context("Getting all installed packages")
+    }
+
+    /// Resolve a shortened version like `cabal/3.10` to the latest installed
+    /// version that starts with it. The installed versions are those from the last
+    /// call of [`Backend::get_all_installed_packages`]. Packages that cannot be
+    /// resolved are returned as they are.
+    fn as_installed(&self, package: &Package) -> Package {
+        let Ok(ghcup_package) = GhcupPackage::try_from(package) else {
+            return package.clone();
+        };
+
+        self.installed
+            .get()
+            .into_iter()
+            .flatten()
+            .rev()
+            .find(|version| ghcup_package.matches(version))
+            .map_or_else(|| package.clone(), Package::from)
+    }
+
+    /// Install the specified packages. ghcup installs one version at a time.
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let packages = GhcupPackage::from_pacdef_packages(packages)?;
+
+        for package in packages {
+            run_external_command(
+                self.build_command(self.backend_info().switches_install, &package),
+            )
+            .with_context(|| format!("installing {package:?}"))?;
+        }
+
+        Ok(())
+    }
+
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let packages = GhcupPackage::from_pacdef_packages(packages)?;
+
+        for package in packages {
+            run_external_command(self.build_command(self.backend_info().switches_remove, &package))
+                .with_context(|| format!("removing {package:?}"))?;
+        }
+
+        Ok(())
+    }
+
+    fn show_package_info(&self, package: &Package) -> Result<()> {
+        let package = GhcupPackage::try_from(package)?;
+        run_read_only_command(self.build_command(self.backend_info().switches_info, &package))
+    }
+}
+
+impl Ghcup {
+    /// Return the installed versions of all tools, ordered from oldest to newest.
+    fn get_installed_versions(&self) -> Result<Vec<GhcupPackage>> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(["list", "-r", "-c", "installed"]);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+
+        Ok(output.lines().filter_map(parse_list_line).collect())
+    }
+
+    fn build_command(&self, switches: Switches, package: &GhcupPackage) -> Command {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(switches);
+        cmd.arg(package.tool.to_string());
+        cmd.arg(&package.version);
+        cmd
+    }
+}
+
+/// Parse a line of `ghcup list -r`, which starts with the tool and its version,
+/// followed by tags and notes. Lines of tools that are not managed are ignored.
+fn parse_list_line(line: &str) -> Option<GhcupPackage> {
+    let mut columns = line.split_whitespace();
+    let tool = Tool::try_from(columns.next()?).ok()?;
+    let version = columns.next()?;
+
+    Some(GhcupPackage::new(tool, version.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_and_match() {
+        let output = "ghc 9.4.8 recommended,base-4.17.2.1 hls-powered\n\
+                      cabal 3.10.2.1 recommended\n\
+                      hls 2.5.0.0 latest\n\
+                      ghcup 0.1.20.0 latest,recommended\n";
+
+        let installed: Vec<_> = output.lines().filter_map(parse_list_line).collect();
+        let names: Vec<_> = installed.iter().map(Package::from).collect();
+        assert_eq!(
+            names,
+            [
+                Package::from("ghc/9.4.8"),
+                Package::from("cabal/3.10.2.1"),
+                Package::from("hls/2.5.0.0")
+            ]
+        );
+
+        let cabal = GhcupPackage::new(Tool::Cabal, "3.10".into());
+        assert!(cabal.matches(&installed[1]));
+
+        let cabal = GhcupPackage::new(Tool::Cabal, "3.1".into());
+        assert!(!cabal.matches(&installed[1]));
+
+        let ghcup = Ghcup::new();
+        assert_eq!(
+            ghcup.as_installed(&Package::from("cabal/3.10")),
+            Package::from("cabal/3.10")
+        );
+
+        ghcup.installed.set(installed).expect("not set yet");
+        assert_eq!(
+            ghcup.as_installed(&Package::from("cabal/3.10")),
+            Package::from("cabal/3.10.2.1")
+        );
+        assert_eq!(
+            ghcup.as_installed(&Package::from("ghc/9.2")),
+            Package::from("ghc/9.2")
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt::Display;
+
+use anyhow::{bail, Context, Result};
+
+use crate::prelude::*;
+
+/// The tools that are managed by ghcup. ghcup itself is not managed by pacdef.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Ghc,
+    Cabal,
+    Hls,
+    Stack,
+}
+
+impl Tool {
+    pub fn try_from<T>(value: T) -> Result<Self>
+    where
+        T: AsRef<str>,
+    {
+        let value = value.as_ref();
+        let result = match value {
+            "ghc" => Self::Ghc,
+            "cabal" => Self::Cabal,
+            "hls" => Self::Hls,
+            "stack" => Self::Stack,
+            _ => bail!("{} is not a tool managed by ghcup", value),
+        };
+        Ok(result)
+    }
+}
+
+impl Display for Tool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Ghc => "ghc",
+            Self::Cabal => "cabal",
+            Self::Hls => "hls",
+            Self::Stack => "stack",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A package as used exclusively in the ghcup backend. Contrary to other packages,
+/// this does not have an (optional) repository and a name, but is a tool with a
+/// version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhcupPackage {
+    /// The tool this is a version of.
+    pub tool: Tool,
+    /// The version of the tool. This may be shortened like `3.10`, which refers to
+    /// the latest version starting with it.
+    pub version: String,
+}
+
+impl GhcupPackage {
+    pub fn new(tool: Tool, version: String) -> Self {
+        Self { tool, version }
+    }
+
+    pub fn from_pacdef_packages(packages: &Packages) -> Result<Vec<Self>> {
+        let mut result = vec![];
+
+        for package in packages {
+            let ghcup_package = Self::try_from(package)
+                .with_context(|| format!("converting pacdef package {package} to ghcup package"))?;
+            result.push(ghcup_package);
+        }
+
+        Ok(result)
+    }
+
+    /// Whether the version of `self` is a full version that refers to `other`, or
+    /// a shortened version that `other` starts with.
+    pub fn matches(&self, other: &Self) -> bool {
+        self.tool == other.tool
+            && (self.version == other.version
+                || other.version.starts_with(&format!("{}.", self.version)))
+    }
+}
+
+impl TryFrom<&Package> for GhcupPackage {
+    type Error = anyhow::Error;
+
+    fn try_from(package: &Package) -> Result<Self> {
+        let repo = package.repo.as_ref().context("getting tool from package")?;
+        let tool = Tool::try_from(repo)?;
+
+        Ok(Self::new(tool, package.name.clone()))
+    }
+}
+
+impl From<&GhcupPackage> for Package {
+    fn from(value: &GhcupPackage) -> Self {
+        format!("{}/{}", value.tool, value.version).into()
+    }
+}
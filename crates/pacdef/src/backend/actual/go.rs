@@ -0,0 +1,229 @@
+use std::fs::{read_dir, remove_file};
+use std::io::ErrorKind::NotFound;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use crate::cmd::{run_external_command, run_read_only_command};
+use crate::prelude::*;
+use crate::recorder::edit_file;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Go {}
+impl Go {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+impl Default for Go {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Backend for Go {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "go".to_string(),
+            section: "go",
+            switches_info: &["version", "-m"],
+            switches_install: &["install"],
+            switches_noconfirm: &[],
+            switches_remove: &[],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            repositories: true,
+            versions: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let binaries = self
+            .get_installed_binaries()
+            .context("getting installed go binaries")?;
+
+        Ok(binaries
+            .into_iter()
+            .map(|(_, package_path)| package_path.into())
+            .collect())
+    }
+
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        self.get_all_installed_packages()
+            .context("getting all installed packages")
+    }
+
+    /// The version is not part of the package path of an installed binary.
+    fn as_installed(&self, package: &Package) -> Package {
+        let spec = package.to_string();
+        let (package_path, _) = split_version(&spec);
+        package_path.into()
+    }
+
+    /// Install the specified packages. `go install` can only install multiple
+    /// packages at once if they belong to the same module, so each package is
+    /// installed on its own. Packages without version are installed `@latest`.
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        for p in packages {
+            let mut cmd = Command::new(&backend_info.binary);
+            cmd.args(backend_info.switches_install);
+
+            let spec = p.to_string();
+            match split_version(&spec) {
+                (_, Some(_)) => cmd.arg(&spec),
+                (package_path, None) => cmd.arg(format!("{package_path}@latest")),
+            };
+
+            run_external_command(cmd).with_context(|| format!("installing {spec}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Remove the specified packages. Go has no command to uninstall binaries, so
+    /// the binaries are deleted from the bin dir.
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let binaries = self
+            .get_installed_binaries()
+            .context("getting installed go binaries")?;
+
+        for p in packages {
+            let file = find_binary(&binaries, p)?;
+            edit_file(format_args!("delete {}", file.display()), || {
+                remove_file(file).with_context(|| format!("deleting {file:?}"))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn show_package_info(&self, package: &Package) -> Result<()> {
+        let backend_info = self.backend_info();
+        let binaries = self
+            .get_installed_binaries()
+            .context("getting installed go binaries")?;
+
+        let mut cmd = Command::new(backend_info.binary);
+        cmd.args(backend_info.switches_info);
+        cmd.arg(find_binary(&binaries, package)?);
+
+        run_read_only_command(cmd)
+    }
+}
+
+impl Go {
+    /// Return the path to each binary in the go bin dir together with the package
+    /// path it was built from.
+    fn get_installed_binaries(&self) -> Result<Vec<(PathBuf, String)>> {
+        let dir = get_go_bin_dir().context("getting go bin dir")?;
+
+        let entries = match read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == NotFound => {
+                log::warn!("no bin dir found for go. Assuming no binaries installed yet.");
+                return Ok(vec![]);
+            }
+            Err(err) => bail!(err),
+        };
+
+        let mut files = vec![];
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+
+        if files.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(self.backend_info().switches_info);
+        cmd.args(&files);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        Ok(parse_version_output(&output))
+    }
+}
+
+/// Split a package like `golang.org/x/tools/gopls@latest` into the package path
+/// and the optional version.
+fn split_version(spec: &str) -> (&str, Option<&str>) {
+    match spec.split_once('@') {
+        Some((package_path, version)) => (package_path, Some(version)),
+        None => (spec, None),
+    }
+}
+
+fn find_binary<'a>(binaries: &'a [(PathBuf, String)], package: &Package) -> Result<&'a PathBuf> {
+    let spec = package.to_string();
+    let (package_path, _) = split_version(&spec);
+
+    binaries
+        .iter()
+        .find(|(_, path)| path == package_path)
+        .map(|(file, _)| file)
+        .with_context(|| format!("no binary found for {package_path}"))
+}
+
+/// Parse the output of `go version -m <file>...`. Each binary starts with a line
+/// `<file>: <go version>`, followed by tab-indented build information. The line
+/// `\tpath\t<package path>` contains the package the binary was built from.
+fn parse_version_output(output: &str) -> Vec<(PathBuf, String)> {
+    let mut result = vec![];
+    let mut current_file = None;
+
+    for line in output.lines() {
+        if let Some(package_path) = line.strip_prefix("\tpath\t") {
+            if let Some(file) = current_file.take() {
+                result.push((file, package_path.trim().to_string()));
+            }
+        } else if !line.starts_with('\t') {
+            current_file = line.rsplit_once(": ").map(|(file, _)| PathBuf::from(file));
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::parse_version_output;
+
+    #[test]
+    fn version_output() {
+        let output = "/home/user/go/bin/gopls: go1.21.5
+\tpath\tgolang.org/x/tools/gopls
+\tmod\tgolang.org/x/tools/gopls\tv0.14.2\th1:abc=
+\tdep\tgolang.org/x/mod\tv0.14.0\th1:def=
+/home/user/go/bin/staticcheck: go1.21.5
+\tpath\thonnef.co/go/tools/cmd/staticcheck
+";
+
+        let result = parse_version_output(output);
+        assert_eq!(
+            result,
+            [
+                (
+                    PathBuf::from("/home/user/go/bin/gopls"),
+                    "golang.org/x/tools/gopls".to_string()
+                ),
+                (
+                    PathBuf::from("/home/user/go/bin/staticcheck"),
+                    "honnef.co/go/tools/cmd/staticcheck".to_string()
+                ),
+            ]
+        );
+    }
+}
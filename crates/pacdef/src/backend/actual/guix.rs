@@ -0,0 +1,166 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::cmd::{run_external_command, run_read_only_command};
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Guix {
+    /// The profile to manage, the default profile of the user if `None`.
+    pub profile: Option<PathBuf>,
+}
+impl Guix {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            profile: config.guix_profile.clone(),
+        }
+    }
+
+    fn build_command(&self, switches: Switches) -> Command {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(switches);
+
+        if let Some(profile) = &self.profile {
+            cmd.arg("--profile");
+            cmd.arg(profile);
+        }
+
+        cmd
+    }
+}
+
+impl Backend for Guix {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "guix".to_string(),
+            section: "guix",
+            switches_info: &["show"],
+            switches_install: &["install"],
+            switches_noconfirm: &[],
+            switches_remove: &["remove"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            versions: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let mut cmd = self.build_command(&["package", "--list-installed"]);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        Ok(parse_list_installed(&output))
+    }
+
+    /// A profile only holds the packages that were installed explicitly, their
+    /// dependencies live in the store.
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        self.get_all_installed_packages()
+            .context("getting all installed packages")
+    }
+
+    /// Versions like `guile@3.0` are only relevant for installing.
+    fn as_installed(&self, package: &Package) -> Package {
+        let spec = package.to_string();
+        let (name, output) = split_output(&spec);
+        let name = name
+            .split('@')
+            .next()
+            .expect("split always yields at least one element");
+
+        output.map_or_else(|| name.into(), |output| format!("{name}:{output}").into())
+    }
+
+    /// Install the specified packages.
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let mut cmd = self.build_command(self.backend_info().switches_install);
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Remove the specified packages.
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let mut cmd = self.build_command(self.backend_info().switches_remove);
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Show information about the package, which does not know about outputs.
+    fn show_package_info(&self, package: &Package) -> Result<()> {
+        let backend_info = self.backend_info();
+        let spec = package.to_string();
+        let (name, _) = split_output(&spec);
+
+        let mut cmd = Command::new(backend_info.binary);
+        cmd.args(backend_info.switches_info);
+        cmd.arg(name);
+
+        run_read_only_command(cmd)
+    }
+}
+
+/// Split a package specification like `glibc:debug` into name and output.
+fn split_output(spec: &str) -> (&str, Option<&str>) {
+    match spec.split_once(':') {
+        Some((name, output)) => (name, Some(output)),
+        None => (spec, None),
+    }
+}
+
+/// Parse the output of `guix package --list-installed`, which holds the name,
+/// version, output and store path of each package, separated by tabs. Outputs
+/// other than the default `out` are written as `name:output`.
+fn parse_list_installed(output: &str) -> Packages {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next()?;
+            let output = fields.nth(1)?;
+
+            if output == "out" {
+                Some(name.into())
+            } else {
+                Some(format!("{name}:{output}").into())
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_installed() {
+        let output = "glibc\t2.35\tdebug\t/gnu/store/abc-glibc-2.35-debug\n\
+                      git\t2.41.0\tsend-email\t/gnu/store/def-git-2.41.0-send-email\n\
+                      hello\t2.12.1\tout\t/gnu/store/ghi-hello-2.12.1\n";
+
+        let names: Vec<_> = parse_list_installed(output)
+            .into_iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert_eq!(names, ["git:send-email", "glibc:debug", "hello"]);
+
+        let guix = Guix { profile: None };
+        assert_eq!(
+            guix.as_installed(&"guile@3.0:debug".into()).to_string(),
+            "guile:debug"
+        );
+    }
+}
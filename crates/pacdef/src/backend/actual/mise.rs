@@ -0,0 +1,227 @@
+use std::fmt::Display;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::cmd::{run_external_command, run_read_only_command};
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Mise {}
+impl Mise {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+impl Default for Mise {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The version mise uses for tools that are requested without a version.
+const LATEST: Text = "latest";
+
+impl Backend for Mise {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "mise".to_string(),
+            section: "mise",
+            switches_info: &["tool"],
+            switches_install: &["use", "--global"],
+            switches_noconfirm: &["--yes"],
+            switches_remove: &["unuse", "--global"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            versions: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(["ls", "--json", "--global"]);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        let json: Value = serde_json::from_str(&output).context("parsing JSON from mise")?;
+
+        Ok(extract_tools(&json)?
+            .into_iter()
+            .map(|tool| tool.to_string().into())
+            .collect())
+    }
+
+    /// The global config only holds the tools the user asked for.
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        self.get_all_installed_packages()
+            .context("getting all installed packages")
+    }
+
+    /// Tools without a version are requested as `latest`.
+    fn as_installed(&self, package: &Package) -> Package {
+        MiseTool::from(package).to_string().into()
+    }
+
+    /// Install the specified packages and add them to the global config.
+    fn install_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = Command::new(backend_info.binary);
+        cmd.args(backend_info.switches_install);
+
+        if noconfirm {
+            cmd.args(backend_info.switches_noconfirm);
+        }
+
+        for p in packages {
+            cmd.arg(MiseTool::from(p).to_string());
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Remove the specified packages from the global config, which also
+    /// uninstalls versions that are no longer used.
+    fn remove_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = Command::new(backend_info.binary);
+        cmd.args(backend_info.switches_remove);
+
+        if noconfirm {
+            cmd.args(backend_info.switches_noconfirm);
+        }
+
+        for p in packages {
+            cmd.arg(MiseTool::from(p).to_string());
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Show information about the tool, regardless of the version.
+    fn show_package_info(&self, package: &Package) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = Command::new(backend_info.binary);
+        cmd.args(backend_info.switches_info);
+        cmd.arg(MiseTool::from(package).tool);
+
+        run_read_only_command(cmd)
+    }
+}
+
+/// A tool as used exclusively in the mise backend. Contrary to other packages, the
+/// name of the tool may contain a backend like `npm:prettier` or `ubi:cli/cli`, and
+/// is followed by the requested version, like `node@20`.
+#[derive(Debug, PartialEq, Eq)]
+struct MiseTool {
+    /// The name of the tool, including its backend if any.
+    tool: String,
+    /// The requested version, which may be a prefix like `20` or `latest`.
+    version: String,
+}
+
+impl MiseTool {
+    fn new(tool: &str, version: Option<&str>) -> Self {
+        Self {
+            tool: tool.to_string(),
+            version: version.unwrap_or(LATEST).to_string(),
+        }
+    }
+}
+
+impl From<&str> for MiseTool {
+    /// Split `tool@version` at the last `@` that does not start a scope, like in
+    /// `npm:@angular/cli@17`.
+    fn from(value: &str) -> Self {
+        let separator = value
+            .char_indices()
+            .rev()
+            .find(|(i, c)| *c == '@' && *i > 0 && !value[..*i].ends_with(':'))
+            .map(|(i, _)| i);
+
+        separator.map_or_else(
+            || Self::new(value, None),
+            |i| Self::new(&value[..i], Some(&value[i + 1..])),
+        )
+    }
+}
+
+impl From<&Package> for MiseTool {
+    fn from(package: &Package) -> Self {
+        Self::from(package.to_string().as_str())
+    }
+}
+
+impl Display for MiseTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.tool, self.version)
+    }
+}
+
+/// Extract the tools from the output of `mise ls --json`. It holds an object with
+/// an array of versions for every tool. Versions that are not installed are
+/// ignored, and the requested version is preferred over the resolved one.
+fn extract_tools(json: &Value) -> Result<Vec<MiseTool>> {
+    let mut result = vec![];
+
+    for (tool, versions) in json.as_object().context("getting object of tools")? {
+        let versions = versions
+            .as_array()
+            .with_context(|| format!("getting versions of {tool}"))?;
+
+        for version in versions {
+            if version["installed"].as_bool() == Some(false) {
+                continue;
+            }
+
+            let requested = version["requested_version"]
+                .as_str()
+                .or_else(|| version["version"].as_str());
+
+            result.push(MiseTool::new(tool, requested));
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tools() {
+        let cases = [
+            ("node@20", "node", "20"),
+            ("python", "python", "latest"),
+            ("npm:@angular/cli@17", "npm:@angular/cli", "17"),
+            ("npm:@angular/cli", "npm:@angular/cli", "latest"),
+        ];
+
+        for (spec, tool, version) in cases {
+            assert_eq!(MiseTool::from(spec), MiseTool::new(tool, Some(version)));
+        }
+
+        let json = serde_json::json!({
+            "node": [
+                {"version": "20.11.0", "requested_version": "20", "installed": true},
+                {"version": "18.19.0", "requested_version": "18", "installed": false}
+            ],
+            "python": [{"version": "3.12.1", "installed": true}]
+        });
+
+        let tools: Vec<_> = extract_tools(&json)
+            .expect("valid output")
+            .into_iter()
+            .map(|tool| tool.to_string())
+            .collect();
+        assert_eq!(tools, ["node@20", "python@3.12.1"]);
+    }
+}
@@ -1,10 +1,30 @@
+pub mod alpine;
+pub mod appimage;
 #[cfg(feature = "arch")]
 pub mod arch;
+pub mod brew;
+pub mod conda;
+pub mod custom;
 #[cfg(feature = "debian")]
 pub mod debian;
 pub mod fedora;
 pub mod flatpak;
+pub mod gem;
+pub mod gentoo;
+pub mod ghcup;
+pub mod go;
+pub mod guix;
+pub mod mise;
+pub mod nix;
+pub mod npm;
+pub mod plugin;
+pub mod podman;
 pub mod python;
 pub mod rust;
 pub mod rustup;
+pub mod snap;
+pub mod suse;
+pub mod systemd;
+pub mod uv;
 pub mod void;
+pub mod vscode;
@@ -0,0 +1,280 @@
+use std::fmt::Display;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+use crate::cmd::{run_external_command, run_read_only_command};
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Nix {}
+impl Nix {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+impl Default for Nix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const SWITCHES_LIST: Switches = &["profile", "list", "--json"];
+
+impl Backend for Nix {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "nix".to_string(),
+            section: "nix",
+            switches_info: &["search"],
+            switches_install: &["profile", "install"],
+            switches_noconfirm: &[],
+            switches_remove: &["profile", "remove"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            repositories: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let elements = self
+            .get_profile_elements()
+            .context("getting elements of nix profile")?;
+
+        Ok(elements
+            .into_iter()
+            .map(|element| element.package.to_string().into())
+            .collect())
+    }
+
+    /// Every element in a nix profile was installed explicitly.
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        self.get_all_installed_packages()
+            .context("getting all installed packages")
+    }
+
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+        let nix_packages = NixPackage::from_pacdef_packages(packages)?;
+
+        let mut cmd = Command::new(backend_info.binary);
+        cmd.args(backend_info.switches_install);
+
+        for p in nix_packages {
+            cmd.arg(p.to_string());
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Remove the specified packages. `nix profile remove` does not accept flake
+    /// references, so we look up the matching profile elements first.
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+        let nix_packages = NixPackage::from_pacdef_packages(packages)?;
+        let elements = self
+            .get_profile_elements()
+            .context("getting elements of nix profile")?;
+
+        let mut cmd = Command::new(backend_info.binary);
+        cmd.args(backend_info.switches_remove);
+
+        for p in &nix_packages {
+            let element = elements
+                .iter()
+                .find(|element| element.package == *p)
+                .with_context(|| format!("{p} is not part of the nix profile"))?;
+            cmd.arg(&element.handle);
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// `nix search` requires a regex, `^` matches the package itself.
+    fn show_package_info(&self, package: &Package) -> Result<()> {
+        let backend_info = self.backend_info();
+        let nix_package = NixPackage::try_from(package)?;
+
+        let mut cmd = Command::new(backend_info.binary);
+        cmd.args(backend_info.switches_info);
+        cmd.arg(nix_package.to_string());
+        cmd.arg("^");
+
+        run_read_only_command(cmd)
+    }
+}
+
+impl Nix {
+    fn get_profile_elements(&self) -> Result<Vec<ProfileElement>> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(SWITCHES_LIST);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        let json: Value = serde_json::from_str(&output).context("parsing JSON from nix")?;
+
+        extract_profile_elements(&json)
+    }
+}
+
+/// A package as used exclusively in the nix backend. It consists of a flake
+/// reference and the attribute within that flake, and is written as
+/// `<flake>#<attribute>` in the group files.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NixPackage {
+    /// The flake reference, like `nixpkgs` or `github:nix-community/home-manager`.
+    pub flake: String,
+    /// The attribute of the package within the flake, like `ripgrep`.
+    pub attribute: String,
+}
+
+impl NixPackage {
+    pub fn from_pacdef_packages(packages: &Packages) -> Result<Vec<Self>> {
+        let mut result = vec![];
+
+        for package in packages {
+            let nix_package = Self::try_from(package)
+                .with_context(|| format!("converting pacdef package {package} to nix package"))?;
+            result.push(nix_package);
+        }
+
+        Ok(result)
+    }
+
+    /// Convert the `originalUrl` and `attrPath` of an element from `nix profile list`
+    /// to a package. The prefix `flake:` is dropped for flakes from the registry,
+    /// and the output type and system are removed from the attribute path.
+    fn from_profile_element(original_url: &str, attr_path: &str) -> Self {
+        let flake = original_url
+            .strip_prefix("flake:")
+            .unwrap_or(original_url)
+            .to_string();
+
+        let mut parts = attr_path.splitn(3, '.');
+        let attribute = match (parts.next(), parts.next(), parts.next()) {
+            (Some("legacyPackages" | "packages"), Some(_system), Some(attribute)) => attribute,
+            _ => attr_path,
+        }
+        .to_string();
+
+        Self { flake, attribute }
+    }
+}
+
+impl TryFrom<&Package> for NixPackage {
+    type Error = anyhow::Error;
+
+    /// The package is converted back to its string representation first, since
+    /// flake references may contain slashes that were interpreted as the repo.
+    fn try_from(package: &Package) -> Result<Self> {
+        let spec = package.to_string();
+
+        let Some((flake, attribute)) = spec.split_once('#') else {
+            bail!("{spec} is not of the form <flake>#<attribute>");
+        };
+
+        if flake.is_empty() || attribute.is_empty() {
+            bail!("{spec} is not of the form <flake>#<attribute>");
+        }
+
+        Ok(Self {
+            flake: flake.to_string(),
+            attribute: attribute.to_string(),
+        })
+    }
+}
+
+impl Display for NixPackage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}#{}", self.flake, self.attribute)
+    }
+}
+
+/// An installed element of the nix profile.
+struct ProfileElement {
+    /// The argument that identifies this element for `nix profile remove`.
+    handle: String,
+    package: NixPackage,
+}
+
+/// Extract the elements from the output of `nix profile list --json`. Since
+/// version 3 of the format the elements are an object keyed by their name,
+/// before they were an array. Elements that were not installed from a flake
+/// (e.g. plain store paths) are ignored.
+fn extract_profile_elements(json: &Value) -> Result<Vec<ProfileElement>> {
+    let elements = json.get("elements").context("getting 'elements' field")?;
+
+    let named: Vec<(Option<&String>, &Value)> = if let Some(object) = elements.as_object() {
+        object
+            .iter()
+            .map(|(name, value)| (Some(name), value))
+            .collect()
+    } else {
+        elements
+            .as_array()
+            .context("'elements' is neither object nor array")?
+            .iter()
+            .map(|value| (None, value))
+            .collect()
+    };
+
+    let result = named
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let original_url = value.get("originalUrl")?.as_str()?;
+            let attr_path = value.get("attrPath")?.as_str()?;
+            let handle = name.map_or_else(|| attr_path.to_string(), Clone::clone);
+
+            Some(ProfileElement {
+                handle,
+                package: NixPackage::from_profile_element(original_url, attr_path),
+            })
+        })
+        .collect();
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn nix_package_from_pacdef_package() {
+        let package = Package::from("github:nix-community/home-manager#default");
+        let nix_package = NixPackage::try_from(&package).expect("valid flake reference");
+        assert_eq!(nix_package.flake, "github:nix-community/home-manager");
+        assert_eq!(nix_package.attribute, "default");
+
+        assert!(NixPackage::try_from(&Package::from("ripgrep")).is_err());
+    }
+
+    #[test]
+    fn profile_elements() {
+        let json = json!({
+            "elements": {
+                "ripgrep": {
+                    "attrPath": "legacyPackages.x86_64-linux.ripgrep",
+                    "originalUrl": "flake:nixpkgs",
+                },
+                "store-path": {
+                    "attrPath": null,
+                    "originalUrl": null,
+                },
+            },
+            "version": 3,
+        });
+
+        let elements = extract_profile_elements(&json).expect("valid profile");
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0].handle, "ripgrep");
+        assert_eq!(elements[0].package.to_string(), "nixpkgs#ripgrep");
+    }
+}
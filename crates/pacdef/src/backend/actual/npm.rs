@@ -0,0 +1,101 @@
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Npm {
+    pub binary: String,
+}
+impl Npm {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            binary: config.npm_binary.clone(),
+        }
+    }
+}
+
+/// These switches list the top-level global packages as JSON. They are understood
+/// by both npm and pnpm.
+const SWITCHES_LIST: Switches = &["ls", "--global", "--depth=0", "--json"];
+
+impl Backend for Npm {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: self.binary.clone(),
+            section: "npm",
+            switches_info: &["view"],
+            switches_install: &["add", "--global"],
+            switches_noconfirm: &[],
+            switches_remove: &["uninstall", "--global"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(SWITCHES_LIST);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        let json: Value = serde_json::from_str(&output)
+            .with_context(|| format!("parsing JSON from {}", self.binary))?;
+
+        extract_packages(&json)
+    }
+
+    /// Only the top-level global packages are listed, all of which were installed
+    /// explicitly.
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        self.get_all_installed_packages()
+            .context("getting all installed packages")
+    }
+}
+
+/// Extract the package names from the output of `ls --json`. npm prints a single
+/// object, whereas pnpm prints an array with one object per global directory.
+/// Scoped packages like `@angular/cli` keep their scope as part of the name.
+fn extract_packages(json: &Value) -> Result<Packages> {
+    let roots: Vec<&Value> = json
+        .as_array()
+        .map_or_else(|| vec![json], |array| array.iter().collect());
+
+    let mut result = Packages::new();
+
+    for root in roots {
+        let Some(dependencies) = root.get("dependencies") else {
+            continue;
+        };
+
+        let dependencies = dependencies
+            .as_object()
+            .context("getting 'dependencies' object")?;
+
+        result.extend(dependencies.keys().map(|name| Package::from(name.as_str())));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn scoped_packages() {
+        let json = json!({
+            "dependencies": {
+                "@angular/cli": { "version": "17.0.0" },
+                "typescript": { "version": "5.3.2" },
+            }
+        });
+
+        let packages = extract_packages(&json).expect("valid npm output");
+        let names: Vec<_> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["@angular/cli", "typescript"]);
+        assert!(packages.iter().all(|p| p.repo.is_none()));
+    }
+}
@@ -0,0 +1,407 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs::read_dir;
+use std::io::Write;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{bail, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::env::should_print_debug_info;
+use crate::prelude::*;
+use crate::recorder::is_dry_run;
+
+/// The version of the protocol between pacdef and its plugins. It is sent with
+/// every request, and plugins must respond with the same version.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The prefix of the executables of plugins. The rest of the name is the section
+/// in the group files.
+const PREFIX: Text = "pacdef-backend-";
+
+/// A backend that is provided by an executable `pacdef-backend-<name>` in `PATH`.
+///
+/// For every action, pacdef runs the executable once, writes a [`Request`] as JSON
+/// to its stdin and reads a [`Response`] as JSON from its stdout. Anything the
+/// plugin prints to stderr is shown to the user.
+#[derive(Debug, Clone)]
+pub struct Plugin {
+    /// The name of the plugin, which is the section in the group files.
+    pub name: Text,
+    /// The path of the executable.
+    pub path: PathBuf,
+    /// The optional requests the plugin supports. They are queried once and
+    /// shared by all clones of the plugin.
+    supported: Arc<OnceLock<PluginCapabilities>>,
+}
+
+// The supported requests are a cache and not part of the identity of the plugin.
+impl PartialEq for Plugin {
+    fn eq(&self, other: &Self) -> bool {
+        (self.name, &self.path) == (other.name, &other.path)
+    }
+}
+
+impl Eq for Plugin {}
+
+impl PartialOrd for Plugin {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Plugin {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.name, &self.path).cmp(&(other.name, &other.path))
+    }
+}
+
+/// A request from pacdef to a plugin.
+#[derive(Debug, Serialize)]
+struct Request<'a> {
+    version: u32,
+    request: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    packages: Vec<String>,
+    noconfirm: bool,
+}
+
+impl<'a> Request<'a> {
+    fn new(request: &'a str, packages: &Packages, noconfirm: bool) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            request,
+            packages: packages.iter().map(ToString::to_string).collect(),
+            noconfirm,
+        }
+    }
+}
+
+/// The response of a plugin to a [`Request`]. Which fields are set depends on the
+/// request.
+#[derive(Debug, Deserialize)]
+struct Response {
+    version: u32,
+    /// Set if the request failed.
+    #[serde(default)]
+    error: Option<String>,
+    /// The result of `list_explicit` and `list_all`.
+    #[serde(default)]
+    packages: Vec<String>,
+    /// The result of `capabilities`.
+    #[serde(default)]
+    capabilities: PluginCapabilities,
+    /// The result of `info`.
+    #[serde(default)]
+    info: Option<String>,
+}
+
+impl Response {
+    /// Parse the output of plugin `name` for `request`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output is not a valid response, has a different
+    /// protocol version or an error, or if the plugin exited with a non-zero
+    /// `status`.
+    fn parse(name: &str, request: &str, stdout: &str, status: ExitStatus) -> Result<Self> {
+        let response: Self = serde_json::from_str(stdout).with_context(|| {
+            format!("plugin {name} returned an invalid response to {request} ({status})")
+        })?;
+
+        ensure!(
+            response.version == PROTOCOL_VERSION,
+            "plugin {name} uses protocol version {}, but pacdef uses {PROTOCOL_VERSION}",
+            response.version
+        );
+
+        if let Some(error) = response.error {
+            bail!("plugin {name} failed to {request}: {error}");
+        }
+
+        ensure!(status.success(), "plugin {name} failed with {status}");
+
+        Ok(response)
+    }
+}
+
+/// The optional requests a plugin supports. Listing, installing and removing
+/// packages must always be supported.
+#[derive(Debug, Default, Deserialize)]
+struct PluginCapabilities {
+    #[serde(default)]
+    make_dependency: bool,
+    #[serde(default)]
+    info: bool,
+}
+
+/// Find all plugins in `PATH`. If the same plugin exists in multiple dirs, the
+/// first one takes precedence, like it does for the shell. The result is
+/// determined once and sorted by name.
+pub fn discover_plugins() -> &'static [Plugin] {
+    static PLUGINS: OnceLock<Vec<Plugin>> = OnceLock::new();
+
+    PLUGINS.get_or_init(|| {
+        let Some(paths) = env::var_os("PATH") else {
+            return vec![];
+        };
+
+        let mut names = BTreeSet::new();
+        let mut result = vec![];
+
+        for dir in env::split_paths(&paths) {
+            let Ok(entries) = read_dir(&dir) else {
+                continue;
+            };
+
+            for entry in entries.flatten() {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+
+                let Some(name) = file_name.strip_prefix(PREFIX) else {
+                    continue;
+                };
+
+                let path = entry.path();
+                if name.is_empty() || !is_executable(&path) || names.contains(name) {
+                    continue;
+                }
+
+                names.insert(name.to_string());
+                result.push(Plugin {
+                    name: name.to_string().leak(),
+                    path,
+                    supported: Arc::default(),
+                });
+            }
+        }
+
+        result.sort_unstable();
+        result
+    })
+}
+
+fn is_executable(path: &Path) -> bool {
+    path.metadata()
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+impl Plugin {
+    /// Find the plugin for a section.
+    pub fn find(section: &str) -> Option<Self> {
+        discover_plugins()
+            .iter()
+            .find(|plugin| plugin.name == section)
+            .cloned()
+    }
+
+    /// Send a request to the plugin and return its response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin cannot be run, exits with a non-zero status,
+    /// responds with an error or with a different protocol version.
+    fn request(&self, request: &str, packages: &Packages, noconfirm: bool) -> Result<Response> {
+        let request = Request::new(request, packages, noconfirm);
+        let json = serde_json::to_string(&request)?;
+
+        if should_print_debug_info() {
+            println!("will send the following request to {}", self.path.display());
+            println!("{json}");
+        }
+
+        let mut child = Command::new(&self.path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("running plugin {}", self.path.display()))?;
+
+        child
+            .stdin
+            .take()
+            .context("getting stdin of plugin")?
+            .write_all(json.as_bytes())
+            .context("sending request to plugin")?;
+
+        let output = child.wait_with_output()?;
+        let stdout = String::from_utf8(output.stdout)?;
+
+        Response::parse(self.name, request.request, &stdout, output.status)
+    }
+
+    /// Send a request that changes the system and ignore the response. In a dry
+    /// run, the request is only printed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, see [`Plugin::request`].
+    fn request_change(&self, request: &str, packages: &Packages, noconfirm: bool) -> Result<()> {
+        if is_dry_run() {
+            let request = Request::new(request, packages, noconfirm);
+            println!(
+                "would send to {}: {}",
+                self.path.display(),
+                serde_json::to_string(&request)?
+            );
+            return Ok(());
+        }
+
+        self.request(request, packages, noconfirm)?;
+        Ok(())
+    }
+
+    fn query_capabilities(&self) -> Result<PluginCapabilities> {
+        Ok(self
+            .request("capabilities", &Packages::new(), false)?
+            .capabilities)
+    }
+
+    /// Query the optional requests the plugin supports on first use. If the query
+    /// fails, a warning is shown and no optional requests are supported.
+    fn supported(&self) -> &PluginCapabilities {
+        self.supported
+            .get_or_init(|| match self.query_capabilities() {
+                Ok(supported) => supported,
+                Err(error) => {
+                    log::warn!(
+                        "plugin {} did not report its capabilities: {error:#}",
+                        self.name
+                    );
+                    PluginCapabilities::default()
+                }
+            })
+    }
+}
+
+impl Backend for Plugin {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: format!("{PREFIX}{}", self.name),
+            section: self.name,
+            switches_info: &[],
+            switches_install: &[],
+            switches_noconfirm: &[],
+            switches_remove: &[],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        let supported = self.supported();
+
+        Capabilities {
+            make_dependency: supported.make_dependency,
+            show_info: supported.info,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let response = self.request("list_all", &Packages::new(), false)?;
+        Ok(response.packages.into_iter().map(Package::from).collect())
+    }
+
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        let response = self.request("list_explicit", &Packages::new(), false)?;
+        Ok(response.packages.into_iter().map(Package::from).collect())
+    }
+
+    fn install_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
+        self.request_change("install", packages, noconfirm)
+    }
+
+    fn make_dependency(&self, packages: &Packages) -> Result<()> {
+        self.ensure_supported(Operation::MakeDependency)?;
+
+        self.request_change("make_dependency", packages, false)
+    }
+
+    fn remove_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
+        self.request_change("remove", packages, noconfirm)
+    }
+
+    fn show_package_info(&self, package: &Package) -> Result<()> {
+        self.ensure_supported(Operation::ShowInfo)?;
+
+        let packages = Packages::from([package.clone()]);
+        let response = self.request("info", &packages, false)?;
+
+        if let Some(info) = response.info {
+            println!("{info}");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::process::ExitStatusExt;
+
+    use super::*;
+
+    fn parse(stdout: &str, code: i32) -> Result<Response> {
+        Response::parse("demo", "install", stdout, ExitStatus::from_raw(code << 8))
+    }
+
+    #[test]
+    fn request() {
+        let packages = Packages::from(["foo".into(), "repo/bar".into()]);
+        assert_eq!(
+            serde_json::to_string(&Request::new("install", &packages, true)).expect("valid json"),
+            r#"{"version":1,"request":"install","packages":["repo/bar","foo"],"noconfirm":true}"#
+        );
+
+        assert_eq!(
+            serde_json::to_string(&Request::new("list_explicit", &Packages::new(), false))
+                .expect("valid json"),
+            r#"{"version":1,"request":"list_explicit","noconfirm":false}"#
+        );
+    }
+
+    #[test]
+    fn response() {
+        let response =
+            parse(r#"{"version": 1, "packages": ["foo", "bar"]}"#, 0).expect("valid response");
+        assert_eq!(response.packages, ["foo", "bar"]);
+        assert!(!response.capabilities.make_dependency);
+        assert_eq!(response.info, None);
+
+        let response = parse(
+            r#"{"version": 1, "capabilities": {"make_dependency": true}}"#,
+            0,
+        )
+        .expect("valid response");
+        assert!(response.capabilities.make_dependency);
+        assert!(!response.capabilities.info);
+    }
+
+    #[test]
+    fn invalid_response() {
+        let error = |stdout, code| {
+            parse(stdout, code)
+                .expect_err("invalid response")
+                .to_string()
+        };
+
+        assert_eq!(
+            error(r#"{"version": 2}"#, 0),
+            "plugin demo uses protocol version 2, but pacdef uses 1"
+        );
+        assert_eq!(
+            error(r#"{"version": 1, "error": "no network"}"#, 1),
+            "plugin demo failed to install: no network"
+        );
+        assert_eq!(
+            error(r#"{"version": 1}"#, 1),
+            "plugin demo failed with exit status: 1"
+        );
+        assert_eq!(
+            error("not json", 0),
+            "plugin demo returned an invalid response to install (exit status: 0)"
+        );
+    }
+}
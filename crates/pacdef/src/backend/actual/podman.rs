@@ -0,0 +1,225 @@
+use std::fmt::Display;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::cmd::run_external_command;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Podman {
+    pub binary: String,
+}
+impl Podman {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            binary: config.podman_binary.clone(),
+        }
+    }
+}
+
+/// The registry that is used for images without a registry.
+const DEFAULT_REGISTRY: Text = "docker.io";
+/// The namespace of official images on the default registry.
+const DEFAULT_NAMESPACE: Text = "library";
+/// The tag that is used for images without tag or digest.
+const DEFAULT_TAG: Text = "latest";
+
+impl Backend for Podman {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: self.binary.clone(),
+            section: "podman",
+            switches_info: &["image", "inspect"],
+            switches_install: &["pull"],
+            switches_noconfirm: &[],
+            switches_remove: &["rmi"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            repositories: true,
+            versions: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(["images", "--format", "json"]);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        let names = extract_image_names(&output)
+            .with_context(|| format!("parsing images from {}", self.binary))?;
+
+        Ok(names
+            .iter()
+            .map(|name| ImageReference::from(name.as_str()).to_string().into())
+            .collect())
+    }
+
+    /// Images are only pulled on request.
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        self.get_all_installed_packages()
+            .context("getting all installed packages")
+    }
+
+    /// Short references like `alpine` are compared in their fully qualified form
+    /// `docker.io/library/alpine:latest`.
+    fn as_installed(&self, package: &Package) -> Package {
+        ImageReference::from(package.to_string().as_str())
+            .to_string()
+            .into()
+    }
+
+    /// Install the specified packages. Only one image can be pulled at a time.
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        for p in packages {
+            let mut cmd = Command::new(&backend_info.binary);
+            cmd.args(backend_info.switches_install);
+            cmd.arg(format!("{p}"));
+
+            run_external_command(cmd).with_context(|| format!("pulling {p}"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A reference to a container image. Contrary to other packages, the name of an
+/// image may contain any number of `/`, and is followed by either a tag or a
+/// digest.
+#[derive(Debug, PartialEq, Eq)]
+struct ImageReference {
+    /// The registry, like `docker.io` or `localhost:5000`.
+    registry: String,
+    /// The path of the image in the registry, like `library/alpine`.
+    repository: String,
+    /// Either `:<tag>` or `@<digest>`, including the separator.
+    version: String,
+}
+
+impl From<&str> for ImageReference {
+    /// Parse and normalize a reference like `alpine`, `quay.io/podman/stable:v4` or
+    /// `localhost:5000/app@sha256:<hash>`. The first component is only a
+    /// registry if it contains a `.` or `:`, or is `localhost`.
+    fn from(value: &str) -> Self {
+        let (name, version) = match value.split_once('@') {
+            Some((name, digest)) => (name, format!("@{digest}")),
+            None => {
+                let last_slash = value.rfind('/').map_or(0, |i| i + 1);
+
+                match value[last_slash..].split_once(':') {
+                    Some((_, tag)) => (&value[..value.len() - tag.len() - 1], format!(":{tag}")),
+                    None => (value, format!(":{DEFAULT_TAG}")),
+                }
+            }
+        };
+
+        let (registry, repository) = match name.split_once('/') {
+            Some((first, rest)) if first.contains(['.', ':']) || first == "localhost" => {
+                (first.to_string(), rest.to_string())
+            }
+            _ => (DEFAULT_REGISTRY.to_string(), name.to_string()),
+        };
+
+        let repository = if registry == DEFAULT_REGISTRY && !repository.contains('/') {
+            format!("{DEFAULT_NAMESPACE}/{repository}")
+        } else {
+            repository
+        };
+
+        Self {
+            registry,
+            repository,
+            version,
+        }
+    }
+}
+
+impl Display for ImageReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}{}", self.registry, self.repository, self.version)
+    }
+}
+
+/// Extract the names of all tagged images from the output of `images --format
+/// json`. podman prints an array of objects with the names of each image, whereas
+/// docker prints one object per line with its repository and tag. Untagged images
+/// are ignored.
+fn extract_image_names(output: &str) -> Result<Vec<String>> {
+    let mut result = vec![];
+
+    if output.trim_start().starts_with('[') {
+        let json: Value = serde_json::from_str(output)?;
+
+        for image in json.as_array().context("getting array of images")? {
+            let Some(names) = image["Names"].as_array() else {
+                continue;
+            };
+
+            result.extend(names.iter().filter_map(Value::as_str).map(String::from));
+        }
+
+        return Ok(result);
+    }
+
+    for line in output.lines().filter(|line| !line.trim().is_empty()) {
+        let image: Value = serde_json::from_str(line)?;
+
+        let (Some(repository), Some(tag)) = (image["Repository"].as_str(), image["Tag"].as_str())
+        else {
+            continue;
+        };
+
+        if repository == "<none>" || tag == "<none>" {
+            continue;
+        }
+
+        result.push(format!("{repository}:{tag}"));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_references() {
+        let cases = [
+            ("alpine", "docker.io/library/alpine:latest"),
+            ("alpine:3.19", "docker.io/library/alpine:3.19"),
+            ("bitnami/redis:7.2", "docker.io/bitnami/redis:7.2"),
+            ("quay.io/podman/stable:v4", "quay.io/podman/stable:v4"),
+            ("localhost:5000/app", "localhost:5000/app:latest"),
+            ("alpine@sha256:abc", "docker.io/library/alpine@sha256:abc"),
+        ];
+
+        for (reference, expected) in cases {
+            assert_eq!(ImageReference::from(reference).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn images_output() {
+        let podman = r#"[{"Names": ["docker.io/library/alpine:3.19"]}, {"Dangling": true}]"#;
+        assert_eq!(
+            extract_image_names(podman).expect("valid output"),
+            ["docker.io/library/alpine:3.19"]
+        );
+
+        let docker = "{\"Repository\":\"alpine\",\"Tag\":\"3.19\"}\n\
+                      {\"Repository\":\"<none>\",\"Tag\":\"<none>\"}\n";
+        assert_eq!(
+            extract_image_names(docker).expect("valid output"),
+            ["alpine:3.19"]
+        );
+    }
+}
@@ -60,6 +60,13 @@ impl Backend for Python {
         }
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            noconfirm: self.binary != "pip",
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
     fn get_all_installed_packages(&self) -> Result<Packages> {
         let mut cmd = Command::new(self.backend_info().binary);
         let output = run_pip_command(&mut cmd, self.get_switches_runtime())?;
@@ -71,10 +78,6 @@ impl Backend for Python {
         let output = run_pip_command(&mut cmd, self.get_switches_explicit())?;
         self.extract_packages(output)
     }
-
-    fn make_dependency(&self, _packages: &Packages) -> Result<()> {
-        panic!("not supported by {}", self.binary)
-    }
 }
 
 fn run_pip_command(cmd: &mut Command, args: &[&str]) -> Result<Value> {
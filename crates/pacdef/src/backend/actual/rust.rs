@@ -54,10 +54,6 @@ impl Backend for Rust {
         self.get_all_installed_packages()
             .context("getting all installed packages")
     }
-
-    fn make_dependency(&self, _: &Packages) -> Result<()> {
-        panic!("not supported by {}", self.backend_info().binary)
-    }
 }
 
 fn extract_packages(json: &Value) -> Result<Packages> {
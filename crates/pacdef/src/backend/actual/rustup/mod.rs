@@ -37,6 +37,13 @@ impl Backend for Rustup {
         }
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            repositories: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
     fn get_all_installed_packages(&self) -> Result<Packages> {
         let toolchains_vec = self
             .run_toolchain_command(Repotype::Toolchain.get_info_switches())
@@ -67,10 +74,6 @@ impl Backend for Rustup {
             .context("Getting all installed packages")
     }
 
-    fn make_dependency(&self, _: &Packages) -> Result<()> {
-        panic!("Not supported by {}", self.backend_info().binary)
-    }
-
     fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
         let packages = RustupPackage::from_pacdef_packages(packages)?;
 
@@ -0,0 +1,130 @@
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::backend::root::Escalation;
+use crate::cmd::run_external_command;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Snap {
+    pub escalation: Escalation,
+}
+impl Snap {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            escalation: Escalation::new(config),
+        }
+    }
+}
+
+/// Snaps with any of these notes are installed by snapd itself to provide a
+/// base or runtime for other snaps.
+const IMPLICIT_NOTES: [&str; 3] = ["base", "core", "snapd"];
+
+impl Backend for Snap {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "snap".to_string(),
+            section: "snap",
+            switches_info: &["info"],
+            switches_install: &["install"],
+            switches_noconfirm: &[],
+            switches_remove: &["remove"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_root: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let snaps = self.get_installed_snaps().context("running snap list")?;
+        Ok(snaps.into_iter().map(|(name, _)| name.into()).collect())
+    }
+
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        let snaps = self.get_installed_snaps().context("running snap list")?;
+
+        Ok(snaps
+            .into_iter()
+            .filter(|(_, notes)| !notes.split(',').any(|note| IMPLICIT_NOTES.contains(&note)))
+            .map(|(name, _)| name.into())
+            .collect())
+    }
+
+    /// Install the specified packages. `snap` applies options like `--channel` or
+    /// `--classic` to all snaps of a command, so every package that has arguments
+    /// is installed on its own.
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let (with_arguments, without_arguments): (Vec<_>, Vec<_>) =
+            packages.iter().partition(|p| !p.arguments.is_empty());
+
+        if !without_arguments.is_empty() {
+            let mut cmd = self.escalation.build_command(self, &backend_info.binary);
+            cmd.args(backend_info.switches_install);
+
+            for p in without_arguments {
+                cmd.arg(format!("{p}"));
+            }
+
+            run_external_command(cmd)?;
+        }
+
+        for p in with_arguments {
+            let mut cmd = self.escalation.build_command(self, &backend_info.binary);
+            cmd.args(backend_info.switches_install);
+            cmd.args(&p.arguments);
+            cmd.arg(format!("{p}"));
+
+            run_external_command(cmd).with_context(|| format!("installing {p}"))?;
+        }
+
+        Ok(())
+    }
+
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
+        cmd.args(backend_info.switches_remove);
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+}
+
+impl Snap {
+    /// Return the name and the notes of all installed snaps.
+    fn get_installed_snaps(&self) -> Result<Vec<(String, String)>> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.arg("list");
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        Ok(parse_snap_list(&output))
+    }
+}
+
+/// Parse the output of `snap list`. The first line is the header, and the columns
+/// are name, version, revision, tracking channel, publisher and notes.
+fn parse_snap_list(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let columns: Vec<_> = line.split_whitespace().collect();
+            let name = columns.first()?;
+            let notes = columns.get(5).unwrap_or(&"-");
+            Some(((*name).to_string(), (*notes).to_string()))
+        })
+        .collect()
+}
@@ -0,0 +1,217 @@
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+use crate::backend::root::Escalation;
+use crate::cmd::run_external_command;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Suse {
+    pub escalation: Escalation,
+}
+impl Suse {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            escalation: Escalation::new(config),
+        }
+    }
+}
+
+/// Patterns are written with this prefix in the group files, which is also the
+/// syntax `zypper` understands for installing and removing them.
+const PATTERN_PREFIX: Text = "pattern:";
+
+/// These switches are responsible for
+/// getting all packages and patterns installed on the system
+const SWITCHES_FETCH_GLOBAL: Switches = &["--no-refresh", "--xmlout", "search", "--installed-only"];
+
+/// These switches are responsible for
+/// getting the packages explicitly installed by the user
+const SWITCHES_FETCH_USER: Switches = &["--no-refresh", "packages", "--userinstalled"];
+
+impl Backend for Suse {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "zypper".to_string(),
+            section: "suse",
+            switches_info: &["info"],
+            switches_install: &["install"],
+            switches_noconfirm: &["--no-confirm"],
+            switches_remove: &["remove"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_root: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let solvables = self
+            .get_installed_solvables()
+            .context("searching installed packages")?;
+
+        Ok(solvables.into_iter().map(|(_, package)| package).collect())
+    }
+
+    /// User-installed packages and all installed patterns are considered explicit.
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(SWITCHES_FETCH_USER);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        let mut packages = parse_package_table(&output);
+
+        let patterns = self
+            .get_installed_solvables()
+            .context("searching installed patterns")?
+            .into_iter()
+            .filter(|(kind, _)| kind == "pattern")
+            .map(|(_, package)| package);
+        packages.extend(patterns);
+
+        Ok(packages)
+    }
+
+    /// Install the specified packages.
+    fn install_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
+        cmd.args(backend_info.switches_install);
+
+        if noconfirm {
+            cmd.args(backend_info.switches_noconfirm);
+        }
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Remove the specified packages.
+    fn remove_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = self.escalation.build_command(self, &backend_info.binary);
+        cmd.args(backend_info.switches_remove);
+
+        if noconfirm {
+            cmd.args(backend_info.switches_noconfirm);
+        }
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+}
+
+impl Suse {
+    /// Return the kind and the package of all installed packages and patterns.
+    fn get_installed_solvables(&self) -> Result<Vec<(String, Package)>> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(SWITCHES_FETCH_GLOBAL);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        parse_solvables(&output)
+    }
+}
+
+/// Parse the `<solvable>` elements from the XML output of `zypper search`. Only
+/// packages and patterns are returned, the latter prefixed with `pattern:`.
+fn parse_solvables(xml: &str) -> Result<Vec<(String, Package)>> {
+    let re_solvable = Regex::new(r"<solvable ([^>]*)>")?;
+    let re_attribute = Regex::new(r#"([a-z-]+)="([^"]*)""#)?;
+
+    let mut result = vec![];
+
+    for solvable in re_solvable.captures_iter(xml) {
+        let mut kind = None;
+        let mut name = None;
+
+        for attribute in re_attribute.captures_iter(&solvable[1]) {
+            match &attribute[1] {
+                "kind" => kind = Some(attribute[2].to_string()),
+                "name" => name = Some(attribute[2].to_string()),
+                _ => (),
+            }
+        }
+
+        let (Some(kind), Some(name)) = (kind, name) else {
+            continue;
+        };
+
+        let package = match kind.as_str() {
+            "package" => name.into(),
+            "pattern" => format!("{PATTERN_PREFIX}{name}").into(),
+            _ => continue,
+        };
+
+        result.push((kind, package));
+    }
+
+    Ok(result)
+}
+
+/// Parse the table printed by `zypper packages`. The columns are status,
+/// repository, name, version and architecture. Installed packages have a status
+/// starting with `i`.
+fn parse_package_table(output: &str) -> Packages {
+    output
+        .lines()
+        .filter_map(|line| {
+            let columns: Vec<_> = line.split('|').map(str::trim).collect();
+            match columns.as_slice() {
+                [status, _, name, ..] if status.starts_with('i') => Some(Package::from(*name)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solvables() {
+        let xml = r#"<?xml version='1.0'?>
+<stream>
+<search-result version="0.0">
+<solvable-list>
+<solvable status="installed" name="vim" summary="Vi IMproved" kind="package"/>
+<solvable status="installed" name="devel_basis" summary="Base Development" kind="pattern"/>
+<solvable status="installed" name="openSUSE" summary="openSUSE Tumbleweed" kind="product"/>
+</solvable-list>
+</search-result>
+</stream>"#;
+
+        let result = parse_solvables(xml).expect("valid regex");
+        let packages: Vec<_> = result.iter().map(|(_, p)| p.to_string()).collect();
+        assert_eq!(packages, ["vim", "pattern:devel_basis"]);
+    }
+
+    #[test]
+    fn package_table() {
+        let output = "Loading repository data...
+Reading installed packages...
+S  | Repository   | Name | Version | Arch
+---+--------------+------+---------+-------
+i+ | repo-oss     | vim  | 9.1     | x86_64
+i+ | repo-oss     | git  | 2.43    | x86_64
+";
+
+        let packages = parse_package_table(output);
+        let names: Vec<_> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["git", "vim"]);
+    }
+}
@@ -0,0 +1,206 @@
+use std::process::Command;
+
+use anyhow::Result;
+
+use crate::backend::root::Escalation;
+use crate::cmd::{run_external_command, run_read_only_command};
+use crate::prelude::*;
+
+/// Manages the enabled systemd units, either of the system or of the user. The
+/// packages are unit names, and installing a package means enabling the unit.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Systemd {
+    /// Whether the units of the user (`systemctl --user`) are managed.
+    pub user: bool,
+    pub escalation: Escalation,
+}
+impl Systemd {
+    pub fn system(config: &Config) -> Self {
+        Self {
+            user: false,
+            escalation: Escalation::new(config),
+        }
+    }
+
+    pub fn user(config: &Config) -> Self {
+        Self {
+            user: true,
+            escalation: Escalation::new(config),
+        }
+    }
+
+    /// Build a `systemctl` command for either the system or the user. Changes to
+    /// system units require root privileges.
+    fn build_command(&self, switches: Switches, privileged: bool) -> Command {
+        let binary = self.backend_info().binary;
+
+        let mut cmd = if self.user {
+            let mut cmd = Command::new(binary);
+            cmd.arg("--user");
+            cmd
+        } else if privileged {
+            self.escalation.build_command(self, &binary)
+        } else {
+            Command::new(binary)
+        };
+
+        cmd.args(switches);
+        cmd
+    }
+
+    /// Return the name of every enabled unit, and whether it is enabled by the
+    /// vendor preset.
+    fn get_enabled_units(&self) -> Result<Vec<(String, bool)>> {
+        let mut cmd = self.build_command(
+            &[
+                "list-unit-files",
+                "--state=enabled",
+                "--no-legend",
+                "--no-pager",
+            ],
+            false,
+        );
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        Ok(output.lines().filter_map(parse_unit_file_line).collect())
+    }
+}
+
+/// The unit type that is assumed if the name of a unit has none.
+const DEFAULT_UNIT_TYPE: Text = "service";
+
+/// All unit types that can be enabled.
+const UNIT_TYPES: [&str; 7] = [
+    "service",
+    "socket",
+    "timer",
+    "path",
+    "target",
+    "mount",
+    "automount",
+];
+
+impl Backend for Systemd {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "systemctl".to_string(),
+            section: if self.user { "systemd-user" } else { "systemd" },
+            switches_info: &["status", "--no-pager"],
+            switches_install: &["enable", "--now"],
+            switches_noconfirm: &[],
+            switches_remove: &["disable"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_root: !self.user,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        Ok(self
+            .get_enabled_units()?
+            .into_iter()
+            .map(|(unit, _)| unit.into())
+            .collect())
+    }
+
+    /// Units that are enabled by the vendor preset were not enabled by the user.
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        Ok(self
+            .get_enabled_units()?
+            .into_iter()
+            .filter(|(_, by_preset)| !by_preset)
+            .map(|(unit, _)| unit.into())
+            .collect())
+    }
+
+    /// Units without type like `docker` refer to the service.
+    fn as_installed(&self, package: &Package) -> Package {
+        add_unit_type(&package.to_string()).into()
+    }
+
+    /// Enable and start the specified units.
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let mut cmd = self.build_command(self.backend_info().switches_install, true);
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Disable the specified units.
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let mut cmd = self.build_command(self.backend_info().switches_remove, true);
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    fn show_package_info(&self, package: &Package) -> Result<()> {
+        let mut cmd = self.build_command(self.backend_info().switches_info, false);
+        cmd.arg(format!("{package}"));
+
+        run_read_only_command(cmd)
+    }
+}
+
+/// Parse a line of `systemctl list-unit-files`, which holds the unit, its state
+/// and its vendor preset.
+fn parse_unit_file_line(line: &str) -> Option<(String, bool)> {
+    let mut columns = line.split_whitespace();
+    let unit = columns.next()?;
+    let preset = columns.nth(1);
+
+    Some((unit.to_string(), preset == Some("enabled")))
+}
+
+/// Append the default unit type to a unit name without type.
+fn add_unit_type(unit: &str) -> String {
+    let has_type = unit
+        .rsplit_once('.')
+        .is_some_and(|(_, unit_type)| UNIT_TYPES.contains(&unit_type));
+
+    if has_type {
+        unit.to_string()
+    } else {
+        format!("{unit}.{DEFAULT_UNIT_TYPE}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_files() {
+        let output = "bluetooth.service enabled enabled\n\
+                      docker.socket enabled disabled\n\
+                      fstrim.timer enabled -\n";
+
+        let units: Vec<_> = output.lines().filter_map(parse_unit_file_line).collect();
+        assert_eq!(
+            units,
+            [
+                ("bluetooth.service".to_string(), true),
+                ("docker.socket".to_string(), false),
+                ("fstrim.timer".to_string(), false)
+            ]
+        );
+
+        assert_eq!(add_unit_type("docker"), "docker.service");
+        assert_eq!(add_unit_type("docker.socket"), "docker.socket");
+        assert_eq!(
+            add_unit_type("systemd-networkd-wait-online"),
+            "systemd-networkd-wait-online.service"
+        );
+    }
+}
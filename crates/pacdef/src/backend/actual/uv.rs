@@ -0,0 +1,162 @@
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::cmd::run_external_command;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Uv {}
+impl Uv {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn get_tool_list(&self) -> Result<String> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(["tool", "list"]);
+
+        Ok(String::from_utf8(cmd.output()?.stdout)?)
+    }
+}
+impl Default for Uv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Backend for Uv {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: "uv".to_string(),
+            section: "uv",
+            switches_info: &[],
+            switches_install: &["tool", "install"],
+            switches_noconfirm: &[],
+            switches_remove: &["tool", "uninstall"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            versions: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let output = self.get_tool_list().context("running uv tool list")?;
+        Ok(parse_tool_list(&output)
+            .into_iter()
+            .map(|(name, _)| name.into())
+            .collect())
+    }
+
+    /// Every tool was installed explicitly, its dependencies live in the
+    /// environment of the tool.
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        self.get_all_installed_packages()
+            .context("getting all installed packages")
+    }
+
+    /// Extras and version specifiers like `black[jupyter]==24.2.0` are only
+    /// relevant for installing.
+    fn as_installed(&self, package: &Package) -> Package {
+        let spec = package.to_string();
+        spec.split(['[', '=', '<', '>', '~', '!', '@'])
+            .next()
+            .expect("split always yields at least one element")
+            .into()
+    }
+
+    /// Install the specified packages. Every tool is installed into its own
+    /// environment, so the arguments of a package like `--with <extra>` only apply
+    /// to that tool.
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        for p in packages {
+            let mut cmd = Command::new(&backend_info.binary);
+            cmd.args(backend_info.switches_install);
+            cmd.arg(format!("{p}"));
+            cmd.args(&p.arguments);
+
+            run_external_command(cmd).with_context(|| format!("installing {p}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Remove the specified packages.
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = Command::new(backend_info.binary);
+        cmd.args(backend_info.switches_remove);
+
+        for p in packages {
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// There is no command to show information about a tool, so its version and
+    /// executables are printed instead.
+    fn show_package_info(&self, package: &Package) -> Result<()> {
+        let name = self.as_installed(package).to_string();
+        let output = self.get_tool_list().context("running uv tool list")?;
+
+        let (_, lines) = parse_tool_list(&output)
+            .into_iter()
+            .find(|(tool, _)| *tool == name)
+            .with_context(|| format!("tool {name} is not installed"))?;
+
+        for line in lines {
+            println!("{line}");
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse the output of `uv tool list`. Every tool is listed as `name version`,
+/// followed by its executables as `- executable`. Return the name of each tool
+/// together with all lines that belong to it.
+fn parse_tool_list(output: &str) -> Vec<(&str, Vec<&str>)> {
+    let mut result: Vec<(&str, Vec<&str>)> = vec![];
+
+    for line in output.lines().filter(|line| !line.trim().is_empty()) {
+        if line.starts_with('-') {
+            if let Some((_, lines)) = result.last_mut() {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        let name = line.split_whitespace().next().expect("line is not empty");
+        result.push((name, vec![line]));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_list() {
+        let output = "black v24.2.0\n- black\n- blackd\nruff v0.3.0\n- ruff\n";
+
+        let tools = parse_tool_list(output);
+        let names: Vec<_> = tools.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["black", "ruff"]);
+        assert_eq!(tools[0].1, ["black v24.2.0", "- black", "- blackd"]);
+
+        let uv = Uv::new();
+        let package = "black[jupyter]==24.2.0".into();
+        assert_eq!(uv.as_installed(&package).to_string(), "black");
+    }
+}
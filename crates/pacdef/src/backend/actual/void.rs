@@ -3,20 +3,19 @@ use std::process::Command;
 use anyhow::Result;
 use regex::Regex;
 
-use crate::backend::root::build_base_command_with_privileges;
-use crate::cmd::run_external_command;
+use crate::backend::root::Escalation;
+use crate::cmd::{run_external_command, run_read_only_command};
 use crate::prelude::*;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Void {}
-impl Void {
-    pub fn new() -> Self {
-        Self {}
-    }
+pub struct Void {
+    pub escalation: Escalation,
 }
-impl Default for Void {
-    fn default() -> Self {
-        Self::new()
+impl Void {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            escalation: Escalation::new(config),
+        }
     }
 }
 
@@ -38,6 +37,13 @@ impl Backend for Void {
         }
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_root: true,
+            ..Capabilities::from(&self.backend_info())
+        }
+    }
+
     fn get_all_installed_packages(&self) -> Result<Packages> {
         // Removes the package status and description from output
         let re_str_1 = r"^ii |^uu |^hr |^\?\? | .*";
@@ -82,7 +88,7 @@ impl Backend for Void {
     fn install_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
         let backend_info = self.backend_info();
 
-        let mut cmd = build_base_command_with_privileges(INSTALL_BINARY);
+        let mut cmd = self.escalation.build_command(self, INSTALL_BINARY);
         cmd.args(backend_info.switches_install);
 
         if noconfirm {
@@ -99,7 +105,7 @@ impl Backend for Void {
     fn remove_packages(&self, packages: &Packages, noconfirm: bool) -> Result<()> {
         let backend_info = self.backend_info();
 
-        let mut cmd = build_base_command_with_privileges(REMOVE_BINARY);
+        let mut cmd = self.escalation.build_command(self, REMOVE_BINARY);
         cmd.args(backend_info.switches_remove);
 
         if noconfirm {
@@ -116,7 +122,7 @@ impl Backend for Void {
     fn make_dependency(&self, packages: &Packages) -> Result<()> {
         let backend_info = self.backend_info();
 
-        let mut cmd = build_base_command_with_privileges(PKGDB_BINARY);
+        let mut cmd = self.escalation.build_command(self, PKGDB_BINARY);
         cmd.args(
             backend_info
                 .switches_make_dependency
@@ -138,6 +144,6 @@ impl Backend for Void {
         cmd.args(backend_info.switches_info);
         cmd.arg(format!("{package}"));
 
-        run_external_command(cmd)
+        run_read_only_command(cmd)
     }
 }
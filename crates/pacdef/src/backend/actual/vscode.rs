@@ -0,0 +1,107 @@
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::cmd::run_external_command;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct VsCode {
+    pub binary: String,
+}
+impl VsCode {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            binary: config.vscode_binary.clone(),
+        }
+    }
+
+    /// Return the installed extensions as `<publisher>.<name>@<version>`.
+    fn get_installed_extensions_with_versions(&self) -> Result<Vec<String>> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.args(["--list-extensions", "--show-versions"]);
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        Ok(output.lines().map(String::from).collect())
+    }
+}
+
+impl Backend for VsCode {
+    fn backend_info(&self) -> BackendInfo {
+        BackendInfo {
+            binary: self.binary.clone(),
+            section: "vscode",
+            switches_info: &[],
+            switches_install: &["--install-extension"],
+            switches_noconfirm: &[],
+            switches_remove: &["--uninstall-extension"],
+            switches_make_dependency: None,
+        }
+    }
+
+    fn get_all_installed_packages(&self) -> Result<Packages> {
+        let mut cmd = Command::new(self.backend_info().binary);
+        cmd.arg("--list-extensions");
+
+        let output = String::from_utf8(cmd.output()?.stdout)?;
+        Ok(output
+            .lines()
+            .map(|extension| extension.to_lowercase().into())
+            .collect())
+    }
+
+    fn get_explicitly_installed_packages(&self) -> Result<Packages> {
+        self.get_all_installed_packages()
+            .context("getting all installed packages")
+    }
+
+    /// Extension identifiers are case-insensitive.
+    fn as_installed(&self, package: &Package) -> Package {
+        package.to_string().to_lowercase().into()
+    }
+
+    /// Install the specified packages. Every extension needs its own switch.
+    fn install_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = Command::new(backend_info.binary);
+
+        for p in packages {
+            cmd.args(backend_info.switches_install);
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// Remove the specified packages. Every extension needs its own switch.
+    fn remove_packages(&self, packages: &Packages, _: bool) -> Result<()> {
+        let backend_info = self.backend_info();
+
+        let mut cmd = Command::new(backend_info.binary);
+
+        for p in packages {
+            cmd.args(backend_info.switches_remove);
+            cmd.arg(format!("{p}"));
+        }
+
+        run_external_command(cmd)
+    }
+
+    /// There is no command to show information about an extension, so the
+    /// installed version is printed instead.
+    fn show_package_info(&self, package: &Package) -> Result<()> {
+        let extension = self.as_installed(package).to_string();
+        let prefix = format!("{extension}@");
+
+        let installed = self
+            .get_installed_extensions_with_versions()?
+            .into_iter()
+            .find(|line| line.to_lowercase().starts_with(&prefix))
+            .with_context(|| format!("extension {extension} is not installed"))?;
+
+        println!("{installed}");
+
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
 use std::collections::BTreeMap;
+use std::fmt::Display;
 use std::process::Command;
 
 use anyhow::Result;
 
-use crate::cmd::run_external_command;
+use crate::cmd::{run_external_command, run_read_only_command};
 use crate::prelude::*;
 
 pub type Switches = &'static [&'static str];
@@ -30,14 +31,107 @@ pub struct BackendInfo {
     pub switches_make_dependency: Option<Switches>,
 }
 
+/// The optional features of a backend. Listing, installing and removing packages
+/// is supported by every backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Packages can be marked as dependency, see [`Backend::make_dependency`].
+    pub make_dependency: bool,
+    /// Information about a package can be shown, see [`Backend::show_package_info`].
+    pub show_info: bool,
+    /// Packages may be qualified with a repository, like `repo/name`.
+    pub repositories: bool,
+    /// Packages may specify the version that is installed.
+    pub versions: bool,
+    /// Packages can be installed and removed without confirmation, either because
+    /// the package manager never asks or because it has a switch to skip it.
+    pub noconfirm: bool,
+    /// pacdef runs the commands that install and remove packages with root
+    /// privileges, see [`Escalation`](crate::backend::root::Escalation).
+    pub requires_root: bool,
+}
+
+impl From<&BackendInfo> for Capabilities {
+    /// Packages can be marked as dependency if there are switches for it. All
+    /// other capabilities are those of most backends.
+    fn from(value: &BackendInfo) -> Self {
+        Self {
+            make_dependency: value.switches_make_dependency.is_some(),
+            show_info: true,
+            repositories: false,
+            versions: false,
+            noconfirm: true,
+            requires_root: false,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Whether the optional `operation` is supported.
+    pub fn supports(self, operation: Operation) -> bool {
+        match operation {
+            Operation::MakeDependency => self.make_dependency,
+            Operation::ShowInfo => self.show_info,
+        }
+    }
+
+    /// Short descriptions of all supported capabilities, as shown by `pacdef
+    /// version`.
+    pub fn names(self) -> Vec<Text> {
+        [
+            (self.make_dependency, "dependencies"),
+            (self.show_info, "info"),
+            (self.repositories, "repositories"),
+            (self.versions, "versions"),
+            (self.noconfirm, "noconfirm"),
+            (self.requires_root, "root"),
+        ]
+        .into_iter()
+        .filter_map(|(supported, name)| supported.then_some(name))
+        .collect()
+    }
+}
+
+/// An operation that is not supported by every backend, see [`Capabilities`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    MakeDependency,
+    ShowInfo,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Self::MakeDependency => "marking packages as dependency",
+            Self::ShowInfo => "showing package info",
+        };
+        write!(f, "{description}")
+    }
+}
+
 /// The trait of a struct that is used as a backend.
 #[enum_dispatch::enum_dispatch]
 pub trait Backend {
     /// Return the [`BackendInfo`] associated with this backend.
     fn backend_info(&self) -> BackendInfo;
 
-    fn supports_as_dependency(&self) -> bool {
-        self.backend_info().switches_make_dependency.is_some()
+    /// Return the [`Capabilities`] of this backend, which are derived from the
+    /// [`BackendInfo`] by default.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::from(&self.backend_info())
+    }
+
+    /// Check that the optional `operation` is supported by this backend.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Unsupported`] if the backend lacks the capability.
+    fn ensure_supported(&self, operation: Operation) -> Result<()> {
+        if self.capabilities().supports(operation) {
+            Ok(())
+        } else {
+            Err(Error::Unsupported(self.backend_info().section, operation).into())
+        }
     }
 
     /// Get all packages that are installed in the system.
@@ -56,6 +150,14 @@ pub trait Backend {
     /// cannot be determined.
     fn get_explicitly_installed_packages(&self) -> Result<Packages>;
 
+    /// Convert a package from the group files to the form in which it is returned
+    /// by [`Backend::get_all_installed_packages`]. This is used to compare managed
+    /// and installed packages, e.g. when the group files may contain a version that
+    /// is only relevant for installing the package.
+    fn as_installed(&self, package: &Package) -> Package {
+        package.clone()
+    }
+
     /// Assign each of the packages to an individual group by editing the
     /// group files.
     ///
@@ -105,14 +207,13 @@ pub trait Backend {
     /// Mark the packages as non-explicit / dependency using the underlying
     /// package manager.
     ///
-    /// # Panics
-    ///
-    /// This method shall panic when the backend does not support dependent packages.
-    ///
     /// # Errors
     ///
-    /// Returns an error if the external command fails.
+    /// Returns [`Error::Unsupported`] if the backend does not support dependent
+    /// packages, or an error if the external command fails.
     fn make_dependency(&self, packages: &Packages) -> Result<()> {
+        self.ensure_supported(Operation::MakeDependency)?;
+
         let backend_info = self.backend_info();
 
         let mut cmd = Command::new(backend_info.binary);
@@ -154,14 +255,17 @@ pub trait Backend {
     ///
     /// # Errors
     ///
-    /// Returns an error if the external command fails.
+    /// Returns [`Error::Unsupported`] if the backend cannot show package info, or
+    /// an error if the external command fails.
     fn show_package_info(&self, package: &Package) -> Result<()> {
+        self.ensure_supported(Operation::ShowInfo)?;
+
         let backend_info = self.backend_info();
 
         let mut cmd = Command::new(backend_info.binary);
         cmd.args(backend_info.switches_info);
         cmd.arg(format!("{package}"));
 
-        run_external_command(cmd)
+        run_read_only_command(cmd)
     }
 }
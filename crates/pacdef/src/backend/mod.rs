@@ -1,11 +1,13 @@
 pub mod actual;
 pub mod backend_trait;
-mod root;
+mod report;
+pub mod root;
 pub mod todo_per_backend;
 
 use std::fmt::Display;
 
 use crate::prelude::*;
+use actual::plugin::discover_plugins;
 use anyhow::{Context, Result};
 
 /// A backend with its associated managed packages
@@ -28,7 +30,13 @@ impl ManagedBackend {
             .get_explicitly_installed_packages()
             .context("could not get explicitly installed packages")?;
 
-        let diff = installed.difference(&self.packages).cloned().collect();
+        let managed: Packages = self
+            .packages
+            .iter()
+            .map(|p| self.any_backend.as_installed(p))
+            .collect();
+
+        let diff = installed.difference(&managed).cloned().collect();
 
         Ok(diff)
     }
@@ -44,59 +52,138 @@ impl ManagedBackend {
             .get_all_installed_packages()
             .context("could not get installed packages")?;
 
-        let diff = self.packages.difference(&installed).cloned().collect();
+        let diff = self
+            .packages
+            .iter()
+            .filter(|p| !installed.contains(&self.any_backend.as_installed(p)))
+            .cloned()
+            .collect();
 
         Ok(diff)
     }
 }
 
+// Custom backends are much larger than the others, but there are only a few
+// backends per run, so boxing them is not worth it.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 #[enum_dispatch::enum_dispatch(Backend)]
 pub enum AnyBackend {
+    Alpine(Alpine),
+    AppImage(AppImage),
     #[cfg(feature = "arch")]
     Arch(actual::arch::Arch),
+    Brew(Brew),
+    Conda(Conda),
+    Custom(Custom),
     #[cfg(feature = "debian")]
     Debian(actual::debian::Debian),
     Flatpak(Flatpak),
     Fedora(Fedora),
+    Gem(Gem),
+    Gentoo(Gentoo),
+    Ghcup(Ghcup),
+    Go(Go),
+    Guix(Guix),
+    Mise(Mise),
+    Nix(Nix),
+    Npm(Npm),
+    Plugin(Plugin),
+    Podman(Podman),
     Python(Python),
     Rust(Rust),
     Rustup(Rustup),
+    Snap(Snap),
+    Suse(Suse),
+    Systemd(Systemd),
+    Uv(Uv),
     Void(Void),
+    VsCode(VsCode),
 }
 impl AnyBackend {
-    /// Returns an iterator of every variant of backend.
+    /// Returns an iterator of every variant of backend, including the custom
+    /// backends from the config and the plugins in `PATH`.
     pub fn all(config: &Config) -> impl Iterator<Item = Self> {
-        vec![
+        let mut result = vec![
+            Self::Alpine(Alpine::new(config)),
+            Self::AppImage(AppImage::new(config)),
             #[cfg(feature = "arch")]
             Self::Arch(actual::arch::Arch::new(config)),
+            Self::Brew(Brew::new()),
+            Self::Conda(Conda::new(config)),
             #[cfg(feature = "debian")]
-            Self::Debian(actual::debian::Debian::new()),
+            Self::Debian(actual::debian::Debian::new(config)),
             Self::Flatpak(Flatpak::new(config)),
-            Self::Fedora(Fedora::new()),
+            Self::Fedora(Fedora::new(config)),
+            Self::Gem(Gem::new(config)),
+            Self::Gentoo(Gentoo::new(config)),
+            Self::Ghcup(Ghcup::new()),
+            Self::Go(Go::new()),
+            Self::Guix(Guix::new(config)),
+            Self::Mise(Mise::new()),
+            Self::Nix(Nix::new()),
+            Self::Npm(Npm::new(config)),
+            Self::Podman(Podman::new(config)),
             Self::Python(Python::new(config)),
             Self::Rust(Rust::new()),
             Self::Rustup(Rustup::new()),
-            Self::Void(Void::new()),
-        ]
-        .into_iter()
+            Self::Snap(Snap::new(config)),
+            Self::Suse(Suse::new(config)),
+            Self::Systemd(Systemd::system(config)),
+            Self::Systemd(Systemd::user(config)),
+            Self::Uv(Uv::new()),
+            Self::Void(Void::new(config)),
+            Self::VsCode(VsCode::new(config)),
+        ];
+
+        result.extend(
+            config
+                .custom_backends
+                .iter()
+                .map(|custom| Self::Custom(custom.with_escalation(config))),
+        );
+        result.extend(discover_plugins().iter().cloned().map(Self::Plugin));
+        result.into_iter()
     }
 
     pub fn from_section(section: &str, config: &Config) -> Result<Self> {
         match section {
+            "alpine" => Ok(Self::Alpine(Alpine::new(config))),
+            "appimage" => Ok(Self::AppImage(AppImage::new(config))),
             #[cfg(feature = "arch")]
             "arch" => Ok(Self::Arch(actual::arch::Arch::new(config))),
+            "brew" => Ok(Self::Brew(Brew::new())),
+            "conda" => Ok(Self::Conda(Conda::new(config))),
             #[cfg(feature = "debian")]
-            "debian" => Ok(Self::Debian(actual::debian::Debian::new())),
+            "debian" => Ok(Self::Debian(actual::debian::Debian::new(config))),
             "flatpak" => Ok(Self::Flatpak(Flatpak::new(config))),
-            "fedora" => Ok(Self::Fedora(Fedora::new())),
+            "fedora" => Ok(Self::Fedora(Fedora::new(config))),
+            "gem" => Ok(Self::Gem(Gem::new(config))),
+            "gentoo" => Ok(Self::Gentoo(Gentoo::new(config))),
+            "ghcup" => Ok(Self::Ghcup(Ghcup::new())),
+            "go" => Ok(Self::Go(Go::new())),
+            "guix" => Ok(Self::Guix(Guix::new(config))),
+            "mise" => Ok(Self::Mise(Mise::new())),
+            "nix" => Ok(Self::Nix(Nix::new())),
+            "npm" => Ok(Self::Npm(Npm::new(config))),
+            "podman" => Ok(Self::Podman(Podman::new(config))),
             "python" => Ok(Self::Python(Python::new(config))),
             "rust" => Ok(Self::Rust(Rust::new())),
             "rustup" => Ok(Self::Rustup(Rustup::new())),
-            "void" => Ok(Self::Void(Void::new())),
-            _ => Err(anyhow::anyhow!(
-                "no matching backend for the section: {section}"
-            )),
+            "snap" => Ok(Self::Snap(Snap::new(config))),
+            "suse" => Ok(Self::Suse(Suse::new(config))),
+            "systemd" => Ok(Self::Systemd(Systemd::system(config))),
+            "systemd-user" => Ok(Self::Systemd(Systemd::user(config))),
+            "uv" => Ok(Self::Uv(Uv::new())),
+            "void" => Ok(Self::Void(Void::new(config))),
+            "vscode" => Ok(Self::VsCode(VsCode::new(config))),
+            _ => config
+                .custom_backends
+                .iter()
+                .find(|custom| custom.section == section)
+                .map(|custom| Self::Custom(custom.with_escalation(config)))
+                .or_else(|| Plugin::find(section).map(Self::Plugin))
+                .ok_or_else(|| anyhow::anyhow!("no matching backend for the section: {section}")),
         }
     }
 }
@@ -0,0 +1,125 @@
+use std::fmt::Display;
+
+use crate::prelude::*;
+
+/// The outcome of installing or removing the packages of a backend.
+#[derive(Debug)]
+pub enum Outcome {
+    /// The package manager succeeded.
+    Success,
+    /// The package manager failed with the error.
+    Failed(anyhow::Error),
+    /// The backend was not run for the reason.
+    Skipped(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Success => write!(f, "ok"),
+            Self::Failed(error) => match get_exit_code(error) {
+                Some(code) => write!(f, "failed with exit code {code}"),
+                None => write!(f, "failed: {}", error.root_cause()),
+            },
+            Self::Skipped(reason) => write!(f, "skipped ({reason})"),
+        }
+    }
+}
+
+/// Get the exit code of the external command that caused `error`, if any.
+fn get_exit_code(error: &anyhow::Error) -> Option<i32> {
+    error
+        .chain()
+        .find_map(|cause| match cause.downcast_ref::<Error>() {
+            Some(Error::CommandFailed(_, status)) => status.code(),
+            _ => None,
+        })
+}
+
+/// The outcomes of all backends of a run, in the order in which they were
+/// attempted.
+#[derive(Debug, Default)]
+pub struct Report(Vec<(String, Option<usize>, Outcome)>);
+
+impl Report {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the outcome of a backend. `packages` is the number of packages that
+    /// were to be installed or removed, if known.
+    pub fn push(&mut self, backend: &AnyBackend, packages: Option<usize>, outcome: Outcome) {
+        self.0.push((backend.to_string(), packages, outcome));
+    }
+
+    /// The backends that failed.
+    pub fn failed(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .filter(|(_, _, outcome)| matches!(outcome, Outcome::Failed(_)))
+            .map(|(backend, _, _)| backend.clone())
+            .collect()
+    }
+
+    /// Print a table with one row per backend.
+    pub fn show(&self) {
+        let width = self
+            .0
+            .iter()
+            .map(|(backend, _, _)| backend.len())
+            .chain(["backend".len()])
+            .max()
+            .unwrap_or_default();
+
+        println!("{:width$}  packages  result", "backend");
+
+        for (backend, packages, outcome) in &self.0 {
+            let packages = packages.map_or_else(|| "-".to_string(), |n| n.to_string());
+            println!("{backend:width$}  {packages:>8}  {outcome}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    use anyhow::{anyhow, Context};
+
+    use super::*;
+
+    /// An error like that of a command that exits with `code`, wrapped in the
+    /// context that is added when installing packages.
+    fn command_failed(code: i32) -> anyhow::Error {
+        Err::<(), _>(Error::CommandFailed(
+            "pacman --sync foo".into(),
+            ExitStatus::from_raw(code << 8),
+        ))
+        .context("installing packages for arch")
+        .expect_err("always an error")
+    }
+
+    #[test]
+    fn exit_code() {
+        assert_eq!(get_exit_code(&command_failed(1)), Some(1));
+        assert_eq!(get_exit_code(&anyhow!("no response")), None);
+    }
+
+    #[test]
+    fn outcome() {
+        assert_eq!(Outcome::Success.to_string(), "ok");
+        assert_eq!(
+            Outcome::Failed(command_failed(100)).to_string(),
+            "failed with exit code 100"
+        );
+        assert_eq!(
+            Outcome::Failed(anyhow!("no response").context("querying arch")).to_string(),
+            "failed: no response"
+        );
+        assert_eq!(
+            Outcome::Skipped("disabled in config".into()).to_string(),
+            "skipped (disabled in config)"
+        );
+    }
+}
@@ -1,17 +1,131 @@
 use std::process::Command;
 
+use crate::prelude::*;
+
+/// The value of [`Config::privilege_escalation`] that disables escalation.
+const NO_ESCALATION: Text = "none";
+
+/// How commands are run with root privileges, as configured by the user. This is
+/// held by every backend that runs commands as root.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Escalation {
+    /// The tool that runs a command as root, like `sudo` or `doas`, or `None` if
+    /// commands are never escalated.
+    binary: Option<String>,
+    /// The arguments for `binary` before the command.
+    args: Vec<String>,
+    /// The sections of backends whose commands are run as the user.
+    unprivileged_backends: Vec<String>,
+    /// Whether pacdef is run as root, in which case nothing is escalated.
+    is_root: bool,
+}
+
+impl Escalation {
+    pub fn new(config: &Config) -> Self {
+        let binary = &config.privilege_escalation;
+
+        Self {
+            binary: (!binary.is_empty() && binary != NO_ESCALATION).then(|| binary.clone()),
+            args: config.privilege_escalation_args.clone(),
+            unprivileged_backends: config.unprivileged_backends.clone(),
+            is_root: we_are_root(),
+        }
+    }
+
+    /// Build a command that runs `binary` for `backend`. The command is escalated
+    /// with the configured tool if the backend [requires
+    /// root](Capabilities::requires_root), unless we are root already, escalation
+    /// is disabled, or the backend is configured to run as the user.
+    pub fn build_command<B: Backend>(&self, backend: &B, binary: &str) -> Command {
+        let section = backend.backend_info().section;
+
+        let escalate_with = self.binary.as_ref().filter(|_| {
+            backend.capabilities().requires_root
+                && !self.is_root
+                && !self
+                    .unprivileged_backends
+                    .iter()
+                    .any(|backend| backend == section)
+        });
+
+        escalate_with.map_or_else(
+            || Command::new(binary),
+            |escalation_binary| {
+                let mut cmd = Command::new(escalation_binary);
+                cmd.args(&self.args);
+                cmd.arg(binary);
+                cmd
+            },
+        )
+    }
+}
+
 pub fn we_are_root() -> bool {
     let uid = unsafe { libc::geteuid() };
     uid == 0
 }
 
-pub fn build_base_command_with_privileges(binary: &str) -> Command {
-    let cmd = if we_are_root() {
-        Command::new(binary)
-    } else {
-        let mut cmd = Command::new("sudo");
-        cmd.arg(binary);
-        cmd
-    };
-    cmd
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The escalation from the `config`, as if pacdef was not run as root.
+    fn escalation(config: &str) -> Escalation {
+        let config: Config = toml::from_str(config).expect("valid config");
+        Escalation {
+            is_root: false,
+            ..Escalation::new(&config)
+        }
+    }
+
+    /// The program and arguments of the command for `apk add`.
+    fn command_line(escalation: &Escalation) -> Vec<String> {
+        let alpine = Alpine::new(&Config::default());
+        let mut cmd = escalation.build_command(&alpine, "apk");
+        cmd.arg("add");
+
+        [cmd.get_program()]
+            .into_iter()
+            .chain(cmd.get_args())
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn escalate() {
+        let escalation = escalation(
+            r#"
+            privilege_escalation = "doas"
+            privilege_escalation_args = ["-n", "-u", "root"]
+            "#,
+        );
+        assert_eq!(
+            command_line(&escalation),
+            ["doas", "-n", "-u", "root", "apk", "add"]
+        );
+    }
+
+    #[test]
+    fn no_escalation() {
+        for config in [
+            r#"privilege_escalation = "none""#,
+            r#"privilege_escalation = """#,
+            r#"unprivileged_backends = ["alpine"]"#,
+        ] {
+            assert_eq!(command_line(&escalation(config)), ["apk", "add"]);
+        }
+
+        let as_root = Escalation {
+            is_root: true,
+            ..escalation("")
+        };
+        assert_eq!(command_line(&as_root), ["apk", "add"]);
+    }
+
+    #[test]
+    fn only_backends_that_require_root() {
+        let gem = Gem::new(&Config::default());
+        let cmd = escalation("").build_command(&gem, "gem");
+        assert_eq!(cmd.get_program(), "gem");
+    }
 }
@@ -1,56 +1,153 @@
 use std::fmt::Write;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 
+use crate::backend::report::{Outcome, Report};
 use crate::prelude::*;
 
 /// A vector of tuples containing a Backends and a vector of unmanaged packages
 /// for that backend.
 ///
 /// This struct is used to store a list of unmanaged packages or missing packages
-/// for all backends.
+/// for all backends. It also holds the backends that were not queried
+/// successfully, together with their [`Outcome`].
 #[derive(Debug)]
-pub struct ToDoPerBackend(Vec<(AnyBackend, Packages)>);
+pub struct ToDoPerBackend {
+    todo: Vec<(AnyBackend, Packages)>,
+    not_queried: Vec<(AnyBackend, Outcome)>,
+}
 impl ToDoPerBackend {
     pub fn new() -> Self {
-        Self(vec![])
+        Self {
+            todo: vec![],
+            not_queried: vec![],
+        }
     }
 
     pub fn push(&mut self, item: (AnyBackend, Packages)) {
-        self.0.push(item);
+        self.todo.push(item);
+    }
+
+    /// Remember that `backend` was skipped, so that it shows up in the report of
+    /// [`ToDoPerBackend::install_missing_packages`] and
+    /// [`ToDoPerBackend::remove_unmanaged_packages`].
+    pub fn skip(&mut self, backend: AnyBackend, reason: String) {
+        self.not_queried.push((backend, Outcome::Skipped(reason)));
+    }
+
+    /// Remember that querying `backend` failed, so that it shows up in the report
+    /// like [`ToDoPerBackend::skip`], and counts as failed backend.
+    pub fn fail(&mut self, backend: AnyBackend, error: anyhow::Error) {
+        self.not_queried.push((backend, Outcome::Failed(error)));
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &(AnyBackend, Packages)> {
-        self.0.iter()
+        self.todo.iter()
     }
 
     pub fn nothing_to_do_for_all_backends(&self) -> bool {
-        self.0.iter().all(|(_, diff)| diff.is_empty())
+        self.todo.iter().all(|(_, diff)| diff.is_empty())
     }
 
-    pub fn install_missing_packages(&self, noconfirm: bool) -> Result<()> {
-        for (backend, packages) in &self.0 {
-            if packages.is_empty() {
-                continue;
-            }
-
+    /// Install the missing packages of all backends. See [`ToDoPerBackend::apply`]
+    /// for `keep_going`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packages of any backend cannot be installed.
+    pub fn install_missing_packages(self, noconfirm: bool, keep_going: bool) -> Result<()> {
+        self.apply(keep_going, |backend, packages| {
             backend
                 .install_packages(packages, noconfirm)
-                .with_context(|| format!("installing packages for {backend}"))?;
+                .with_context(|| format!("installing packages for {backend}"))
+        })
+    }
+
+    /// Remove the unmanaged packages of all backends. See
+    /// [`ToDoPerBackend::apply`] for `keep_going`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packages of any backend cannot be removed.
+    pub fn remove_unmanaged_packages(self, noconfirm: bool, keep_going: bool) -> Result<()> {
+        self.apply(keep_going, |backend, packages| {
+            backend
+                .remove_packages(packages, noconfirm)
+                .with_context(|| format!("removing packages for {backend}"))
+        })
+    }
+
+    /// If `keep_going` is `true` and any backend was not queried successfully,
+    /// print the report for a run in which there is nothing to do.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BackendsFailed`] if querying any backend failed.
+    pub fn report_nothing_to_do(self, keep_going: bool) -> Result<()> {
+        if !keep_going || self.not_queried.is_empty() {
+            return Ok(());
         }
-        Ok(())
+
+        self.finish(Report::new())
     }
 
-    pub fn remove_unmanaged_packages(&self, noconfirm: bool) -> Result<()> {
-        for (backend, packages) in &self.0 {
+    /// Run `action` for every backend with packages. By default, the first failing
+    /// backend stops the run. If `keep_going` is `true`, all backends are attempted
+    /// and a report with the outcome of every backend is printed at the end.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first failing backend, or
+    /// [`Error::BackendsFailed`] with all failed backends if `keep_going` is
+    /// `true`. Backends whose query failed count as failed as well.
+    fn apply<F>(self, keep_going: bool, action: F) -> Result<()>
+    where
+        F: Fn(&AnyBackend, &Packages) -> Result<()>,
+    {
+        let mut report = Report::new();
+
+        for (backend, packages) in &self.todo {
             if packages.is_empty() {
                 continue;
             }
 
-            backend
-                .remove_packages(packages, noconfirm)
-                .with_context(|| format!("removing packages for {backend}"))?;
+            if !keep_going {
+                action(backend, packages)?;
+                continue;
+            }
+
+            let outcome = match action(backend, packages) {
+                Ok(()) => Outcome::Success,
+                Err(error) => {
+                    log::error!("{error:#}");
+                    Outcome::Failed(error)
+                }
+            };
+            report.push(backend, Some(packages.len()), outcome);
         }
+
+        if !keep_going {
+            return Ok(());
+        }
+
+        self.finish(report)
+    }
+
+    /// Add the backends that were not queried successfully to `report`, print it
+    /// and return an error if any backend failed.
+    fn finish(self, mut report: Report) -> Result<()> {
+        for (backend, outcome) in self.not_queried {
+            report.push(&backend, None, outcome);
+        }
+
+        println!();
+        report.show();
+
+        let failed = report.failed();
+        if !failed.is_empty() {
+            bail!(Error::BackendsFailed(failed));
+        }
+
         Ok(())
     }
 
@@ -99,6 +196,60 @@ impl IntoIterator for ToDoPerBackend {
     type IntoIter = std::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.into_iter()
+        self.todo.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use anyhow::{anyhow, ensure};
+
+    use super::*;
+
+    /// Three backends with one package each, of which the query of `npm` failed.
+    fn todo() -> ToDoPerBackend {
+        let config = Config::default();
+
+        let mut todo = ToDoPerBackend::new();
+        todo.push((AnyBackend::Rust(Rust::new()), ["ripgrep".into()].into()));
+        todo.push((AnyBackend::Gem(Gem::new(&config)), ["rake".into()].into()));
+        todo.push((AnyBackend::Go(Go::new()), ["gopls".into()].into()));
+        todo.fail(AnyBackend::Npm(Npm::new(&config)), anyhow!("no response"));
+        todo
+    }
+
+    /// Run `apply` with an action that fails for `gem`, and return the result and
+    /// the backends for which the action was run.
+    fn apply(keep_going: bool) -> (Result<()>, Vec<String>) {
+        let attempted = RefCell::new(vec![]);
+
+        let result = todo().apply(keep_going, |backend, _| {
+            attempted.borrow_mut().push(backend.to_string());
+            ensure!(backend.to_string() != "gem", "gem failed");
+            Ok(())
+        });
+
+        (result, attempted.into_inner())
+    }
+
+    #[test]
+    fn keep_going() {
+        let (result, attempted) = apply(true);
+
+        assert_eq!(attempted, ["rust", "gem", "go"]);
+        match result.expect_err("gem and npm failed").downcast::<Error>() {
+            Ok(Error::BackendsFailed(failed)) => assert_eq!(failed, ["gem", "npm"]),
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn stop_at_first_failure() {
+        let (result, attempted) = apply(false);
+
+        assert_eq!(attempted, ["rust", "gem"]);
+        assert_eq!(result.expect_err("gem failed").to_string(), "gem failed");
     }
 }
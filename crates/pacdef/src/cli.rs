@@ -73,6 +73,10 @@ pub struct ExportGroupAction {
     #[arg(short, long)]
     /// overwrite output files if they exist
     pub force: bool,
+
+    #[arg(long)]
+    /// only print the commands and file edits that would be made
+    pub dry_run: bool,
 }
 
 #[derive(Args)]
@@ -82,6 +86,10 @@ pub struct ImportGroupAction {
     #[arg(required(true), num_args(1..))]
     /// the file to import as group
     pub import_groups: Vec<String>,
+
+    #[arg(long)]
+    /// only print the commands and file edits that would be made
+    pub dry_run: bool,
 }
 
 #[derive(Args)]
@@ -109,6 +117,10 @@ pub struct RemoveGroupAction {
     #[arg(required(true), num_args(1..))]
     /// a previously imported group that will be removed
     pub remove_groups: Vec<String>,
+
+    #[arg(long)]
+    /// only print the commands and file edits that would be made
+    pub dry_run: bool,
 }
 
 #[derive(Args)]
@@ -148,12 +160,24 @@ pub struct CleanPackageAction {
     #[arg(long)]
     /// do not ask for any confirmation
     pub no_confirm: bool,
+
+    #[arg(short, long)]
+    /// continue with the other backends if one fails, and print a report
+    pub keep_going: bool,
+
+    #[arg(long)]
+    /// only print the commands and file edits that would be made
+    pub dry_run: bool,
 }
 
 #[derive(Args)]
 #[command(visible_alias("r"))]
 /// review unmanaged packages
-pub struct ReviewPackageAction {}
+pub struct ReviewPackageAction {
+    #[arg(long)]
+    /// only print the commands and file edits that would be made
+    pub dry_run: bool,
+}
 
 #[derive(Args)]
 #[command(arg_required_else_help(true), visible_alias("se"))]
@@ -171,6 +195,14 @@ pub struct SyncPackageAction {
     #[arg(long)]
     /// do not ask for any confirmation
     pub no_confirm: bool,
+
+    #[arg(short, long)]
+    /// continue with the other backends if one fails, and print a report
+    pub keep_going: bool,
+
+    #[arg(long)]
+    /// only print the commands and file edits that would be made
+    pub dry_run: bool,
 }
 
 #[derive(Args)]
@@ -4,6 +4,8 @@ use std::process::Command;
 use anyhow::{ensure, Context, Result};
 
 use crate::env::{get_editor, should_print_debug_info};
+use crate::prelude::*;
+use crate::recorder::skip_command;
 
 /// Run the editor and pass the provided files as arguments. The workdir is set
 /// to the parent of the first file.
@@ -28,14 +30,30 @@ where
     inner(&files)
 }
 
-/// Run an external command. Use the anyhow framework to bubble up errors if they occur. Will print
-/// the full command to be executed when pacdef is in debug mode.
+/// Run an external command that changes the system. Use the anyhow framework to bubble up errors if
+/// they occur. Will print the full command to be executed when pacdef is in debug mode. In a dry run,
+/// the command is only printed.
 ///
 /// # Errors
 ///
 /// This function will return an error if the command cannot be run or if it returns a non-zero
 /// exit status. In case of an error the full command will be part of the error message.
-pub fn run_external_command(mut cmd: Command) -> Result<()> {
+pub fn run_external_command(cmd: Command) -> Result<()> {
+    if skip_command(&cmd) {
+        return Ok(());
+    }
+
+    run_read_only_command(cmd)
+}
+
+/// Run an external command that only shows information, like the info of a package. Unlike
+/// [`run_external_command`], the command is run in a dry run as well.
+///
+/// # Errors
+///
+/// This function will return an error if the command cannot be run or if it returns a non-zero
+/// exit status. In case of an error the full command will be part of the error message.
+pub fn run_read_only_command(mut cmd: Command) -> Result<()> {
     if should_print_debug_info() {
         println!("will run the following command");
         dbg!(&cmd);
@@ -45,10 +63,9 @@ pub fn run_external_command(mut cmd: Command) -> Result<()> {
         .status()
         .with_context(|| format!("running command [{cmd:?}]"))?;
 
-    let success = exit_status.success();
     ensure!(
-        success,
-        "command [{cmd:?}] returned non-zero exit status {success}"
+        exit_status.success(),
+        Error::CommandFailed(format!("{cmd:?}"), exit_status)
     );
     Ok(())
 }
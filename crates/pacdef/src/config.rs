@@ -1,6 +1,6 @@
 use std::fs::{create_dir_all, read_to_string, File};
 use std::io::{ErrorKind, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
@@ -20,21 +20,70 @@ pub struct Config {
     /// Install Flatpak packages system-wide
     #[serde(default = "yes")]
     pub flatpak_systemwide: bool,
+    /// Install Ruby gems into the user's home directory
+    #[serde(default)]
+    pub gem_user_install: bool,
+    /// The directory for managed AppImages, `~/Applications` if unset
+    #[serde(default)]
+    pub appimage_dir: Option<PathBuf>,
+    /// The Guix profile to manage, the default profile of the user if unset
+    #[serde(default)]
+    pub guix_profile: Option<PathBuf>,
     /// Warn the user when a group is not a symlink.
     #[serde(default = "yes")]
     pub warn_not_symlinks: bool,
     /// Backends the user does not want to use even though the binary exists.
     #[serde(default)]
     pub disabled_backends: Vec<String>,
+    /// The tool to run commands with root privileges, like sudo, doas, run0 or
+    /// pkexec. `none` disables escalation.
+    #[serde(default = "privilege_escalation")]
+    pub privilege_escalation: String,
+    /// Additional arguments to pass to `privilege_escalation` before the command.
+    #[serde(default)]
+    pub privilege_escalation_args: Vec<String>,
+    /// Backends whose commands are run as the user instead of with root privileges.
+    #[serde(default)]
+    pub unprivileged_backends: Vec<String>,
+    /// Seconds to wait for a backend to list its packages before skipping it.
+    #[serde(default = "backend_timeout")]
+    pub backend_timeout: u64,
     /// Choose whether to use pipx instead of pip for python package management
     #[serde(default = "pip")]
     pub pip_binary: String,
+    /// Choose whether to use pnpm instead of npm for global Node package management
+    #[serde(default = "npm")]
+    pub npm_binary: String,
+    /// Choose whether to use mamba or micromamba instead of conda
+    #[serde(default = "conda")]
+    pub conda_binary: String,
+    /// The conda environment to manage
+    #[serde(default = "conda_env")]
+    pub conda_env: String,
+    /// Choose whether to use docker instead of podman for container images
+    #[serde(default = "podman")]
+    pub podman_binary: String,
+    /// Choose whether to use VSCodium instead of VS Code for extension management
+    #[serde(default = "vscode")]
+    pub vscode_binary: String,
+    /// Backends defined by the user, used for sections that do not belong to a
+    /// built-in backend.
+    #[serde(default)]
+    pub custom_backends: Vec<Custom>,
 }
 
 fn yes() -> bool {
     true
 }
 
+fn backend_timeout() -> u64 {
+    60
+}
+
+fn privilege_escalation() -> String {
+    "sudo".into()
+}
+
 fn aur_helper() -> String {
     "paru".into()
 }
@@ -43,6 +92,26 @@ fn pip() -> String {
     "pip".into()
 }
 
+fn npm() -> String {
+    "npm".into()
+}
+
+fn conda() -> String {
+    "conda".into()
+}
+
+fn conda_env() -> String {
+    "base".into()
+}
+
+fn podman() -> String {
+    "podman".into()
+}
+
+fn vscode() -> String {
+    "code".into()
+}
+
 impl Config {
     /// Load the config from the associated file.
     ///
@@ -93,9 +162,22 @@ impl Default for Config {
             aur_helper: "paru".into(),
             aur_rm_args: vec![],
             flatpak_systemwide: true,
+            gem_user_install: false,
+            appimage_dir: None,
+            guix_profile: None,
             warn_not_symlinks: true,
             disabled_backends: vec![],
+            privilege_escalation: "sudo".into(),
+            privilege_escalation_args: vec![],
+            unprivileged_backends: vec![],
+            backend_timeout: 60,
             pip_binary: "pip".into(),
+            npm_binary: "npm".into(),
+            conda_binary: "conda".into(),
+            conda_env: "base".into(),
+            podman_binary: "podman".into(),
+            vscode_binary: "code".into(),
+            custom_backends: vec![],
         }
     }
 }
@@ -4,15 +4,20 @@ use std::fs::{copy, create_dir_all, remove_file, rename, File};
 use std::os::unix::fs::symlink;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use anyhow::{bail, ensure, Context, Result};
+use anyhow::{anyhow, bail, ensure, Context, Result};
 use const_format::formatcp;
 
+use crate::backend::actual::plugin::{discover_plugins, PROTOCOL_VERSION};
 use crate::cmd::{run_edit_command, run_external_command};
 use crate::env::{get_editor, should_print_debug_info};
 use crate::grouping::group::groups_to_backend_packages;
 use crate::path::{binary_in_path, get_absolutized_file_paths, get_group_dir};
 use crate::prelude::*;
+use crate::recorder::{edit_file, set_dry_run};
 use crate::review::review;
 use crate::search::search_packages;
 use crate::ui::get_user_confirmation;
@@ -42,9 +47,16 @@ impl VersionArguments {
         let backends = get_included_backends(config);
         let mut result = format!("pacdef, version: {}\n", get_version_string());
         result.push_str("supported backends:");
-        for b in backends {
-            result.push_str("\n  ");
-            result.push_str(b);
+        for (section, capabilities) in backends {
+            result.push_str(&format!(
+                "\n  {section} ({})",
+                capabilities.names().join(", ")
+            ));
+        }
+
+        result.push_str(&format!("\nplugins (protocol version {PROTOCOL_VERSION}):"));
+        for plugin in discover_plugins() {
+            result.push_str(&format!("\n  {} ({})", plugin.name, plugin.path.display()));
         }
 
         println!("{}", result);
@@ -113,6 +125,8 @@ impl ExportGroupAction {
     /// At the moment we cannot export nested group dirs. The user would have to
     /// export every group file individually, or use a shell glob.
     fn run(self, groups: &Groups) -> Result<()> {
+        set_dry_run(self.dry_run);
+
         let groups = find_groups_by_name(&self.export_groups, groups)?;
         let output_dir = match self.output_dir {
             Some(p) => p,
@@ -137,8 +151,16 @@ impl ExportGroupAction {
 
             create_parent(&exported_path)
                 .with_context(|| format!("creating parent dir of {exported_path:?}"))?;
-            move_file(&group.path, &exported_path).context("moving file")?;
-            symlink(&exported_path, &group.path).context("creating symlink to exported file")?;
+            edit_file(
+                format_args!(
+                    "move {} to {}",
+                    group.path.display(),
+                    exported_path.display()
+                ),
+                || move_file(&group.path, &exported_path).context("moving file"),
+            )?;
+            create_symlink(&exported_path, &group.path)
+                .context("creating symlink to exported file")?;
         }
 
         Ok(())
@@ -147,6 +169,8 @@ impl ExportGroupAction {
 
 impl ImportGroupAction {
     fn run(self) -> Result<()> {
+        set_dry_run(self.dry_run);
+
         let files = get_absolutized_file_paths(&self.import_groups)?;
         let groups_dir = get_group_dir()?;
 
@@ -168,7 +192,7 @@ impl ImportGroupAction {
             if link.exists() {
                 log::warn!("group {target_name} already exists, skipping");
             } else {
-                symlink(target, link)?;
+                create_symlink(&target, &link)?;
             }
         }
 
@@ -231,7 +255,10 @@ impl NewGroupAction {
         }
 
         for file in &paths {
-            File::create(file)?;
+            edit_file(format_args!("create {}", file.display()), || {
+                File::create(file)?;
+                Ok(())
+            })?;
         }
 
         if self.edit {
@@ -244,10 +271,15 @@ impl NewGroupAction {
 
 impl RemoveGroupAction {
     fn run(self, groups: &Groups) -> Result<()> {
+        set_dry_run(self.dry_run);
+
         let found = find_groups_by_name(&self.remove_groups, groups)?;
 
         for group in found {
-            remove_file(&group.path)?;
+            edit_file(format_args!("delete {}", group.path.display()), || {
+                remove_file(&group.path)?;
+                Ok(())
+            })?;
         }
 
         Ok(())
@@ -312,11 +344,13 @@ impl PackageArguments {
 
 impl CleanPackageAction {
     fn run(self, groups: &Groups, config: &Config) -> Result<()> {
+        set_dry_run(self.dry_run);
+
         let to_remove = get_unmanaged_packages(groups, config)?;
 
         if to_remove.nothing_to_do_for_all_backends() {
             println!("nothing to do");
-            return Ok(());
+            return to_remove.report_nothing_to_do(self.keep_going);
         }
 
         println!("Would remove the following packages:\n");
@@ -324,17 +358,20 @@ impl CleanPackageAction {
 
         println!();
         if self.no_confirm {
+            warn_about_confirmation(&to_remove);
             println!("proceeding without confirmation");
-        } else if !get_user_confirmation()? {
+        } else if !self.dry_run && !get_user_confirmation()? {
             return Ok(());
         }
 
-        to_remove.remove_unmanaged_packages(self.no_confirm)
+        to_remove.remove_unmanaged_packages(self.no_confirm, self.keep_going)
     }
 }
 
 impl ReviewPackageAction {
     fn run(self, groups: &Groups, config: &Config) -> Result<()> {
+        set_dry_run(self.dry_run);
+
         review(get_unmanaged_packages(groups, config)?, groups)
     }
 }
@@ -347,24 +384,28 @@ impl SearchPackageAction {
 
 impl SyncPackageAction {
     fn run(self, groups: &Groups, config: &Config) -> Result<()> {
+        set_dry_run(self.dry_run);
+
         let to_install = get_missing_packages(groups, config)?;
 
         if to_install.nothing_to_do_for_all_backends() {
             println!("nothing to do");
-            return Ok(());
+            return to_install.report_nothing_to_do(self.keep_going);
         }
 
         println!("Would install the following packages:\n");
         to_install.show().context("printing things to do")?;
+        warn_about_repositories(&to_install);
 
         println!();
         if self.no_confirm {
+            warn_about_confirmation(&to_install);
             println!("proceeding without confirmation");
-        } else if !get_user_confirmation()? {
+        } else if !self.dry_run && !get_user_confirmation()? {
             return Ok(());
         }
 
-        to_install.install_missing_packages(self.no_confirm)
+        to_install.install_missing_packages(self.no_confirm, self.keep_going)
     }
 }
 
@@ -383,36 +424,7 @@ impl UnmanagedPackageAction {
 }
 
 fn get_missing_packages(groups: &Groups, config: &Config) -> Result<ToDoPerBackend> {
-    let backend_packages = groups_to_backend_packages(groups, config)?;
-
-    let mut to_install = ToDoPerBackend::new();
-
-    for (any_backend, packages) in &backend_packages {
-        let backend_info = any_backend.backend_info();
-
-        if config
-            .disabled_backends
-            .contains(&backend_info.section.to_string())
-        {
-            continue;
-        }
-
-        if !binary_in_path(&backend_info.binary)? {
-            continue;
-        }
-
-        let managed_backend = ManagedBackend {
-            packages: packages.clone(),
-            any_backend: any_backend.clone(),
-        };
-
-        match managed_backend.get_missing_packages_sorted() {
-            Ok(diff) => to_install.push((any_backend.clone(), diff)),
-            Err(error) => show_backend_query_error(&error, any_backend),
-        };
-    }
-
-    Ok(to_install)
+    query_backends(groups, config, ManagedBackend::get_missing_packages_sorted)
 }
 
 /// Get a list of unmanaged packages per backend.
@@ -423,35 +435,116 @@ fn get_missing_packages(groups: &Groups, config: &Config) -> Result<ToDoPerBacke
 ///
 /// This function will propagate errors from the individual backends.
 fn get_unmanaged_packages(groups: &Groups, config: &Config) -> Result<ToDoPerBackend> {
+    query_backends(
+        groups,
+        config,
+        ManagedBackend::get_unmanaged_packages_sorted,
+    )
+}
+
+/// Run `query` for all enabled backends whose binary is in `PATH`. The backends
+/// are queried in parallel, and the results are returned in the order of the
+/// backends. A backend that fails or does not respond within the timeout from
+/// the config is skipped with a warning and remembered as failed in the result.
+/// Disabled backends and those whose binary is not in `PATH` are remembered as
+/// skipped.
+///
+/// # Errors
+///
+/// This function will return an error if `PATH` cannot be read.
+fn query_backends(
+    groups: &Groups,
+    config: &Config,
+    query: fn(&ManagedBackend) -> Result<Packages>,
+) -> Result<ToDoPerBackend> {
     let backend_packages = groups_to_backend_packages(groups, config)?;
 
-    let mut todo_unmanaged = ToDoPerBackend::new();
+    let mut jobs = vec![];
+    let mut result = ToDoPerBackend::new();
 
-    for (any_backend, packages) in &backend_packages {
+    for (any_backend, packages) in backend_packages {
         let backend_info = any_backend.backend_info();
+
         if config
             .disabled_backends
             .contains(&backend_info.section.to_string())
         {
+            result.skip(any_backend, "disabled in config".into());
             continue;
         }
 
         if !binary_in_path(&backend_info.binary)? {
+            result.skip(
+                any_backend,
+                format!("{} not found in PATH", backend_info.binary),
+            );
             continue;
         }
 
         let managed_backend = ManagedBackend {
-            packages: packages.clone(),
+            packages,
             any_backend: any_backend.clone(),
         };
 
-        match managed_backend.get_unmanaged_packages_sorted() {
-            Ok(unmanaged) => todo_unmanaged.push((any_backend.clone(), unmanaged)),
-            Err(error) => show_backend_query_error(&error, any_backend),
+        jobs.push((any_backend, move || query(&managed_backend)));
+    }
+
+    let timeout = Duration::from_secs(config.backend_timeout);
+
+    for (any_backend, packages) in run_in_parallel(jobs, timeout) {
+        match packages {
+            Ok(packages) => result.push((any_backend, packages)),
+            Err(error) => {
+                show_backend_query_error(&error, &any_backend);
+                result.fail(any_backend, error);
+            }
         };
     }
 
-    Ok(todo_unmanaged)
+    Ok(result)
+}
+
+/// Run every job in its own thread and return the results in the order of the
+/// jobs, each with its key. A job that does not finish within `timeout`, counted
+/// from the start of all jobs, or that panics results in an error.
+fn run_in_parallel<K, T, F>(jobs: Vec<(K, F)>, timeout: Duration) -> Vec<(K, Result<T>)>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    let deadline = Instant::now() + timeout;
+
+    let receivers: Vec<_> = jobs
+        .into_iter()
+        .map(|(key, job)| {
+            let (sender, receiver) = mpsc::channel();
+
+            // A job that times out keeps running in the background. Its result
+            // is discarded, because nobody is listening anymore.
+            thread::spawn(move || {
+                let _ = sender.send(job());
+            });
+
+            (key, receiver)
+        })
+        .collect();
+
+    receivers
+        .into_iter()
+        .map(|(key, receiver)| {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            let result = match receiver.recv_timeout(remaining) {
+                Ok(result) => result,
+                Err(RecvTimeoutError::Timeout) => {
+                    Err(anyhow!("no response within {} seconds", timeout.as_secs()))
+                }
+                Err(RecvTimeoutError::Disconnected) => Err(anyhow!("query panicked")),
+            };
+
+            (key, result)
+        })
+        .collect()
 }
 
 /// Create the parent directory of the `path` if that directory does not exist.
@@ -468,11 +561,28 @@ fn get_unmanaged_packages(groups: &Groups, config: &Config) -> Result<ToDoPerBac
 fn create_parent(path: &Path) -> Result<()> {
     let parent = &path.parent().expect("this should never be /");
     if !parent.is_dir() {
-        create_dir_all(parent).context("creating parent dir")?;
+        edit_file(format_args!("create dir {}", parent.display()), || {
+            create_dir_all(parent).context("creating parent dir")
+        })?;
     }
     Ok(())
 }
 
+/// Create a symlink at `link` that points to `target`, or print it in a dry run.
+///
+/// # Errors
+///
+/// This function will propagate errors from [`std::os::unix::fs::symlink`].
+fn create_symlink(target: &Path, link: &Path) -> Result<()> {
+    edit_file(
+        format_args!("link {} to {}", link.display(), target.display()),
+        || {
+            symlink(target, link)?;
+            Ok(())
+        },
+    )
+}
+
 /// Move a file from one place to another.
 ///
 /// At first [`std::fs::rename`] is used, which fails if `from` and `to` reside under
@@ -555,12 +665,98 @@ pub const fn get_version_string() -> &'static str {
     }
 }
 
-/// Get a vector with the names of all backends, sorted alphabetically.
-fn get_included_backends(config: &Config) -> Vec<&'static str> {
+/// Warn about backends that will ask for confirmation despite `--noconfirm`.
+fn warn_about_confirmation(todo_per_backend: &ToDoPerBackend) {
+    for (backend, packages) in todo_per_backend.iter() {
+        if !packages.is_empty() && !backend.capabilities().noconfirm {
+            log::warn!("{backend} cannot skip confirmation and may ask anyway");
+        }
+    }
+}
+
+/// Warn about packages that are qualified with a repository in a backend that
+/// does not support repositories, since the package manager will likely not
+/// understand them.
+fn warn_about_repositories(todo_per_backend: &ToDoPerBackend) {
+    for (backend, packages) in todo_per_backend.iter() {
+        if backend.capabilities().repositories {
+            continue;
+        }
+
+        for package in packages.iter().filter(|p| p.repo.is_some()) {
+            log::warn!("{backend} does not support repositories, but {package} has one");
+        }
+    }
+}
+
+/// Get a vector with the names and capabilities of all backends except plugins,
+/// sorted alphabetically.
+fn get_included_backends(config: &Config) -> Vec<(&'static str, Capabilities)> {
     let mut result = vec![];
     for backend in AnyBackend::all(config) {
-        result.push(backend.backend_info().section);
+        if matches!(backend, AnyBackend::Plugin(_)) {
+            continue;
+        }
+        result.push((backend.backend_info().section, backend.capabilities()));
     }
-    result.sort_unstable();
+    result.sort_unstable_by_key(|(section, _)| *section);
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Job = Box<dyn FnOnce() -> Result<u64> + Send>;
+
+    /// A job that returns `value` after `millis` milliseconds.
+    fn sleep_then(millis: u64, value: u64) -> impl FnOnce() -> Result<u64> + Send {
+        move || {
+            thread::sleep(Duration::from_millis(millis));
+            Ok(value)
+        }
+    }
+
+    #[test]
+    fn parallel_in_order() {
+        let jobs: Vec<_> = [300, 100, 200]
+            .into_iter()
+            .enumerate()
+            .map(|(key, millis)| (key, sleep_then(millis, millis)))
+            .collect();
+
+        let start = Instant::now();
+        let results = run_in_parallel(jobs, Duration::from_secs(10));
+
+        assert!(start.elapsed() < Duration::from_millis(550));
+        let results: Vec<_> = results
+            .into_iter()
+            .map(|(key, result)| (key, result.expect("no timeout")))
+            .collect();
+        assert_eq!(results, [(0, 300), (1, 100), (2, 200)]);
+    }
+
+    #[test]
+    fn timeout_and_panic() {
+        let jobs: Vec<(_, Job)> = vec![
+            ("fast", Box::new(sleep_then(0, 1))),
+            ("slow", Box::new(sleep_then(5000, 2))),
+            ("panics", Box::new(|| panic!("broken backend"))),
+        ];
+
+        let results = run_in_parallel(jobs, Duration::from_secs(1));
+
+        let messages: Vec<_> = results
+            .into_iter()
+            .map(|(key, result)| (key, result.map_err(|error| error.to_string())))
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                ("fast", Ok(1)),
+                ("slow", Err("no response within 1 seconds".to_string())),
+                ("panics", Err("query panicked".to_string())),
+            ]
+        );
+    }
+}
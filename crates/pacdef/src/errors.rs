@@ -1,6 +1,9 @@
 use std::error::Error as ErrorTrait;
 use std::fmt::Display;
 use std::path::PathBuf;
+use std::process::ExitStatus;
+
+use crate::backend::backend_trait::{Operation, Text};
 
 /// Error types for pacdef.
 #[derive(Debug)]
@@ -18,6 +21,13 @@ pub enum Error {
     InvalidGroupName(String),
     /// Multiple groups not found.
     MultipleGroupsNotFound(Vec<String>),
+    /// Backend (by section) does not support an operation.
+    Unsupported(Text, Operation),
+    /// External command (as shown in the debug output) returned a non-zero exit
+    /// status.
+    CommandFailed(String, ExitStatus),
+    /// Installing or removing packages failed for multiple backends.
+    BackendsFailed(Vec<String>),
 }
 
 impl Display for Error {
@@ -37,6 +47,15 @@ impl Display for Error {
                     vec.join(", ")
                 )
             }
+            Self::Unsupported(section, operation) => {
+                write!(f, "[{section}] does not support {operation}")
+            }
+            Self::CommandFailed(cmd, status) => {
+                write!(f, "command [{cmd}] failed with {status}")
+            }
+            Self::BackendsFailed(vec) => {
+                write!(f, "the following backends failed: [{}]", vec.join(", "))
+            }
         }
     }
 }
@@ -10,6 +10,7 @@ use path_absolutize::Absolutize;
 use walkdir::WalkDir;
 
 use crate::path::get_relative_path;
+use crate::recorder::edit_file;
 
 use crate::prelude::*;
 
@@ -190,7 +191,8 @@ impl Group {
 
     /// Add the new `packages` to the group file under the section `section_header`. If
     /// the section header does not yet exist, it is created. The packages are written
-    /// in the provided order immediately after the header.
+    /// in the provided order immediately after the header. In a dry run, the edit is
+    /// only printed.
     ///
     /// # Errors
     ///
@@ -207,10 +209,19 @@ impl Group {
             add_new_section_with_packages(&mut content, section_header, packages);
         }
 
-        let mut file = File::create(&self.path)
-            .with_context(|| format!("creating descriptor to output file {:?}", &self.path))?;
+        let names: Vec<_> = packages.iter().map(ToString::to_string).collect();
+        let description = format!(
+            "add {} to {section_header} in {}",
+            names.join(", "),
+            self.path.display()
+        );
 
-        write!(file, "{content}").with_context(|| format!("writing file {:?}", &self.path))
+        edit_file(description, || {
+            let mut file = File::create(&self.path)
+                .with_context(|| format!("creating descriptor to output file {:?}", &self.path))?;
+
+            write!(file, "{content}").with_context(|| format!("writing file {:?}", &self.path))
+        })
     }
 }
 
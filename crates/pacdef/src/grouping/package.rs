@@ -5,20 +5,28 @@ use std::fmt::{Display, Write};
 pub type Packages = BTreeSet<Package>;
 
 /// A struct to represent a single package, consisting of a `name`, and
-/// optionally a `repo`.
+/// optionally a `repo` and `arguments`.
 #[derive(Debug, Clone)]
 pub struct Package {
     /// The name of the package
     pub name: String,
     /// Optionally, which repository the package belongs to
     pub repo: Option<String>,
+    /// Additional arguments for the package manager when installing this
+    /// package. These are not part of the identity of the package.
+    pub arguments: Vec<String>,
 }
 
+/// Remove a trailing comment and surrounding whitespace. A `#` only starts a
+/// comment at the beginning of the line or after whitespace, so that it may be
+/// part of a package, like in the flake reference `nixpkgs#ripgrep`.
 fn remove_comment_and_trim_whitespace(s: &str) -> &str {
-    s.split('#') // remove comment
-        .next()
-        .expect("line contains something")
-        .trim() // remove whitespace
+    let end = s
+        .char_indices()
+        .find(|&(idx, c)| c == '#' && (idx == 0 || s[..idx].ends_with(char::is_whitespace)))
+        .map_or(s.len(), |(idx, _)| idx);
+
+    s[..end].trim()
 }
 
 impl From<String> for Package {
@@ -26,8 +34,7 @@ impl From<String> for Package {
         let trimmed = remove_comment_and_trim_whitespace(&value);
         debug_assert!(!trimmed.is_empty(), "empty package names are not allowed");
 
-        let (name, repo) = Self::split_into_name_and_repo(trimmed);
-        Self { name, repo }
+        Self::from_trimmed(trimmed)
     }
 }
 
@@ -42,10 +49,17 @@ impl Package {
     /// repository, return the package name as well as the repository if it
     /// exists.
     ///
+    /// A leading `@` denotes a scope (like in `@angular/cli` for npm) rather
+    /// than a repository. Scoped names are returned as they are.
+    ///
     /// # Panics
     ///
     /// Panics if `string` is empty.
     fn split_into_name_and_repo(string: &str) -> (String, Option<String>) {
+        if string.starts_with('@') {
+            return (string.to_string(), None);
+        }
+
         if let Some((before, after)) = string.split_once('/') {
             (after.to_string(), Some(before.to_string()))
         } else {
@@ -65,8 +79,24 @@ impl Package {
             return None;
         }
 
-        let (name, repo) = Self::split_into_name_and_repo(trimmed);
-        Some(Self { name, repo })
+        Some(Self::from_trimmed(trimmed))
+    }
+
+    /// Create a package from a line without comment and surrounding whitespace.
+    /// Anything after the first whitespace is split into the `arguments`.
+    fn from_trimmed(trimmed: &str) -> Self {
+        let (spec, arguments) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+
+        let (name, repo) = Self::split_into_name_and_repo(spec);
+        let arguments = arguments.split_whitespace().map(String::from).collect();
+
+        Self {
+            name,
+            repo,
+            arguments,
+        }
     }
 }
 
@@ -122,6 +152,11 @@ mod tests {
         let (name, repo) = super::Package::split_into_name_and_repo(&x);
         assert_eq!(name, "something");
         assert_eq!(repo, None);
+
+        let x = "@scope/name".to_string();
+        let (name, repo) = Package::split_into_name_and_repo(&x);
+        assert_eq!(name, "@scope/name");
+        assert_eq!(repo, None);
     }
 
     #[test]
@@ -131,4 +166,23 @@ mod tests {
         assert_eq!(p.name, "somepackage");
         assert_eq!(p.repo, Some("myrepo".to_string()));
     }
+
+    #[test]
+    fn hash_inside_package() {
+        let p = Package::try_from("nixpkgs#ripgrep # a comment").expect("valid package line");
+        assert_eq!(p.name, "nixpkgs#ripgrep");
+        assert_eq!(p.repo, None);
+
+        assert!(Package::try_from("# only a comment").is_none());
+    }
+
+    #[test]
+    fn arguments() {
+        let p = Package::try_from("code --classic  --channel=latest/edge # editor")
+            .expect("valid package line");
+        assert_eq!(p.name, "code");
+        assert_eq!(p.repo, None);
+        assert_eq!(p.arguments, ["--classic", "--channel=latest/edge"]);
+        assert_eq!(p, Package::from("code"));
+    }
 }
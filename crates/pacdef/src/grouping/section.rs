@@ -108,6 +108,9 @@ impl Display for Section {
 
         while let Some(package) = iter.next() {
             package.fmt(f)?;
+            for argument in &package.arguments {
+                write!(f, " {argument}")?;
+            }
             if iter.peek().is_some() {
                 f.write_char('\n')?;
             }
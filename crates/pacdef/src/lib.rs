@@ -31,6 +31,7 @@ mod core;
 mod env;
 mod errors;
 mod grouping;
+mod recorder;
 mod review;
 mod search;
 mod ui;
@@ -50,6 +50,34 @@ pub fn get_cargo_home() -> Result<PathBuf> {
     }
 }
 
+/// Get the path to the directory where `go install` puts binaries. This is
+/// `$GOBIN`, or `bin` under the first entry of `$GOPATH`, which defaults to
+/// `$HOME/go`.
+///
+/// # Errors
+///
+/// This function will return an error if none of the `$GOBIN`, `$GOPATH` or
+/// `$HOME` environment variables are set.
+pub fn get_go_bin_dir() -> Result<PathBuf> {
+    match env::var("GOBIN") {
+        Ok(gobin) if !gobin.is_empty() => return Ok(gobin.into()),
+        _ => (),
+    }
+
+    let mut dir = match env::var_os("GOPATH") {
+        Some(gopath) if !gopath.is_empty() => env::split_paths(&gopath)
+            .next()
+            .context("getting first entry of $GOPATH")?,
+        _ => {
+            let mut home = get_home_dir().context("falling back to $HOME/go")?;
+            home.push("go");
+            home
+        }
+    };
+    dir.push("bin");
+    Ok(dir)
+}
+
 /// Get the path to the XDG config directory.
 ///
 /// # Errors
@@ -3,9 +3,14 @@ pub use crate::backend::actual::arch::Arch;
 #[cfg(feature = "debian")]
 pub use crate::backend::actual::debian::Debian;
 pub use crate::backend::actual::{
-    fedora::Fedora, flatpak::Flatpak, python::Python, rust::Rust, rustup::Rustup, void::Void,
+    alpine::Alpine, appimage::AppImage, brew::Brew, conda::Conda, custom::Custom, fedora::Fedora,
+    flatpak::Flatpak, gem::Gem, gentoo::Gentoo, ghcup::Ghcup, go::Go, guix::Guix, mise::Mise,
+    nix::Nix, npm::Npm, plugin::Plugin, podman::Podman, python::Python, rust::Rust, rustup::Rustup,
+    snap::Snap, suse::Suse, systemd::Systemd, uv::Uv, void::Void, vscode::VsCode,
+};
+pub use crate::backend::backend_trait::{
+    Backend, BackendInfo, Capabilities, Operation, Switches, Text,
 };
-pub use crate::backend::backend_trait::{Backend, BackendInfo, Switches, Text};
 pub use crate::backend::todo_per_backend::ToDoPerBackend;
 pub use crate::backend::AnyBackend;
 pub use crate::backend::ManagedBackend;
@@ -40,6 +45,7 @@ pub use crate::path::get_absolutized_file_paths;
 pub use crate::path::get_cargo_home;
 pub use crate::path::get_config_path;
 pub use crate::path::get_config_path_old_version;
+pub use crate::path::get_go_bin_dir;
 pub use crate::path::get_group_dir;
 pub use crate::path::get_home_dir;
 pub use crate::path::get_pacdef_base_dir;
@@ -0,0 +1,103 @@
+//! All changes that pacdef makes to the system, i.e. external commands and file
+//! edits, pass through this module. In a dry run, the changes are printed instead
+//! of being applied.
+
+use std::fmt::Display;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable the dry run for the rest of the program.
+pub fn set_dry_run(dry_run: bool) {
+    DRY_RUN.store(dry_run, Ordering::Relaxed);
+}
+
+/// Whether changes are only printed instead of being applied.
+pub fn is_dry_run() -> bool {
+    DRY_RUN.load(Ordering::Relaxed)
+}
+
+/// In a dry run, print the command line of `cmd` and return `true`, in which case
+/// the command must not be run.
+pub fn skip_command(cmd: &Command) -> bool {
+    if is_dry_run() {
+        println!("would run: {}", format_command(cmd));
+    }
+
+    is_dry_run()
+}
+
+/// Apply an edit of the file system, or only print its `description` in a dry
+/// run. The description should read like `delete <file>`.
+///
+/// # Errors
+///
+/// Returns the error of `edit`.
+pub fn edit_file<D, F>(description: D, edit: F) -> Result<()>
+where
+    D: Display,
+    F: FnOnce() -> Result<()>,
+{
+    if is_dry_run() {
+        println!("would {description}");
+        Ok(())
+    } else {
+        edit()
+    }
+}
+
+/// Format a command like it would be typed into a shell, including the
+/// environment variables it sets.
+fn format_command(cmd: &Command) -> String {
+    let envs = cmd.get_envs().filter_map(|(key, value)| {
+        value.map(|value| {
+            format!(
+                "{}={}",
+                key.to_string_lossy(),
+                quote(&value.to_string_lossy())
+            )
+        })
+    });
+
+    let program = quote(&cmd.get_program().to_string_lossy());
+    let args = cmd.get_args().map(|arg| quote(&arg.to_string_lossy()));
+
+    envs.chain([program])
+        .chain(args)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Quote an argument in single quotes if the shell would interpret it otherwise.
+fn quote(arg: &str) -> String {
+    let is_plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+
+    if is_plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_line() {
+        let mut cmd = Command::new("sudo");
+        cmd.args(["pacman", "-S", "--noconfirm", "neovim", "it's here"]);
+        cmd.env("GOBIN", "/home/user/go bin");
+
+        assert_eq!(
+            format_command(&cmd),
+            r"GOBIN='/home/user/go bin' sudo pacman -S --noconfirm neovim 'it'\''s here'"
+        );
+    }
+}
@@ -71,13 +71,11 @@ fn get_action_for_package(
     reviews: &mut Vec<ReviewAction>,
     backend: &dyn Backend,
 ) -> Result<ContinueWithReview> {
+    let capabilities = backend.capabilities();
+
     loop {
-        match ask_user_action_for_package(backend.supports_as_dependency())? {
+        match ask_user_action_for_package(capabilities)? {
             ReviewIntention::AsDependency => {
-                assert!(
-                    backend.supports_as_dependency(),
-                    "backend does not support dependencies"
-                );
                 reviews.push(ReviewAction::AsDependency(package));
                 break;
             }
@@ -109,14 +107,14 @@ fn get_action_for_package(
 /// # Errors
 ///
 /// This function will return an error if stdin or stdout cannot be accessed.
-fn ask_user_action_for_package(supports_as_dependency: bool) -> Result<ReviewIntention> {
-    print_query(supports_as_dependency)?;
+fn ask_user_action_for_package(capabilities: Capabilities) -> Result<ReviewIntention> {
+    print_query(capabilities)?;
 
     match read_single_char_from_terminal()?.to_ascii_lowercase() {
-        'a' if supports_as_dependency => Ok(ReviewIntention::AsDependency),
+        'a' if capabilities.make_dependency => Ok(ReviewIntention::AsDependency),
         'd' => Ok(ReviewIntention::Delete),
         'g' => Ok(ReviewIntention::AssignGroup),
-        'i' => Ok(ReviewIntention::Info),
+        'i' if capabilities.show_info => Ok(ReviewIntention::Info),
         'q' => Ok(ReviewIntention::Quit),
         's' => Ok(ReviewIntention::Skip),
         'p' => Ok(ReviewIntention::Apply),
@@ -125,16 +123,20 @@ fn ask_user_action_for_package(supports_as_dependency: bool) -> Result<ReviewInt
 }
 
 /// Print a space-terminated string that asks the user for the desired action.
-/// The items of the string depend on whether the backend supports dependent
-/// packages.
+/// The items of the string depend on whether the backend supports showing info
+/// and dependent packages.
 ///
 /// # Errors
 ///
 /// This function will return an error if stdout cannot be flushed.
-fn print_query(supports_as_dependency: bool) -> Result<()> {
-    let mut query = String::from("assign to (g)roup, (d)elete, (s)kip, (i)nfo, ");
+fn print_query(capabilities: Capabilities) -> Result<()> {
+    let mut query = String::from("assign to (g)roup, (d)elete, (s)kip, ");
+
+    if capabilities.show_info {
+        query.push_str("(i)nfo, ");
+    }
 
-    if supports_as_dependency {
+    if capabilities.make_dependency {
         query.push_str("(a)s dependency, ");
     }
 
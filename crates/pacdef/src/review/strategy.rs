@@ -25,7 +25,18 @@ impl Strategy {
         }
     }
 
+    /// Apply the reviewed actions. Unsupported actions are rejected before any
+    /// of them is applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Unsupported`] if packages should be marked as dependency
+    /// but the backend cannot do that, or an error if any of the actions fails.
     pub fn execute(self) -> Result<()> {
+        if !self.as_dependency.is_empty() {
+            self.backend.ensure_supported(Operation::MakeDependency)?;
+        }
+
         if !self.delete.is_empty() {
             self.backend.remove_packages(&self.delete, false)?;
         }